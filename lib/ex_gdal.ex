@@ -3,94 +3,1399 @@ defmodule ExGdal do
   Elixir bindings for GDAL via Rustler NIF.
 
   Provides read access to raster datasets (GeoTIFF, etc.) through the GDAL library.
+
+  Functions that change a dataset's pixels or metadata, such as
+  `write_band/4`, `set_no_data_value/3` or `set_geo_transform/2`, need a
+  writable dataset: one from `create/6` or `to_mem/1`, or opened with
+  `open_ex(path, access: :update)`. On a read-only dataset they return
+  `{:error, {:invalid_argument, _}}` rather than writing a `.aux.xml`
+  sidecar.
+  """
+
+  alias ExGdal.{Band, CoordTransform, Dataset, GeoTransform, Native}
+
+  @typedoc """
+  Error reason: a kind atom to match on and a human-readable message.
+
+  Kinds are `:open_failed`, `:invalid_band`, `:invalid_argument`, `:io_error`,
+  `:invalid_layer`, `:closed`, `:all_nodata`, `:no_spatial_ref`,
+  `:transform_failed`, `:timeout` and `:gdal_error` (any other GDAL failure).
+
+  An out-of-range band index is `{:invalid_band, index, band_count}`
+  instead, so the index and count can be matched without parsing text.
+  """
+  @type error ::
+          {atom(), String.t()} | {:invalid_band, non_neg_integer(), non_neg_integer()}
+
+  @typedoc """
+  A pixel window `{x, y, w, h}`: top-left column and row, then width and
+  height. Windowed readers take one of these, and an output size as a
+  `{width, height}` pair, rather than separate arguments.
+  """
+  @type window :: {integer(), integer(), non_neg_integer(), non_neg_integer()}
+
+  @doc """
+  Opens a raster dataset at the given path.
+
+  Returns `{:ok, %Dataset{}}` with cached metadata, or `{:error, reason}`.
+  """
+  @spec open(String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open(path), do: open_ex(path, [])
+
+  @doc """
+  Opens a dataset like `open/1`, with extra control over how GDAL opens it.
+
+  Options:
+
+    * `:access` - `:read_only` (default) or `:update`, which is needed to
+      write bands or set metadata, nodata and georeferencing on an
+      existing file
+    * `:open_options` - driver open options, as `"KEY=VALUE"` strings or
+      `{key, value}` pairs (e.g. `[overview_level: 0]`)
+    * `:drivers` - short names of the only drivers to try, e.g. `["GTiff"]`;
+      by default every registered driver is tried
+  """
+  @spec open_ex(String.t(), keyword()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_ex(path, opts) do
+    path = Path.expand(path)
+    open_options = opts |> Keyword.get(:open_options, []) |> Enum.map(&open_option/1)
+
+    access = Keyword.get(opts, :access, :read_only)
+
+    with {:ok, ref} <- Native.gdal_open_ex(path, access, open_options, opts[:drivers]) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Opens a dataset like `open_ex/2`, and also returns the warnings GDAL
+  emitted while opening, such as ignored TIFF tags or unsupported open
+  options, which are otherwise only printed to stderr.
+
+  Returns `{:ok, dataset, warnings}` with `warnings` a list of messages,
+  empty for a clean file. Takes the options of `open_ex/2`.
+  """
+  @spec open_with_warnings(String.t(), keyword()) ::
+          {:ok, Dataset.t(), [String.t()]} | {:error, error()}
+  def open_with_warnings(path, opts \\ []) do
+    path = Path.expand(path)
+    open_options = opts |> Keyword.get(:open_options, []) |> Enum.map(&open_option/1)
+    access = Keyword.get(opts, :access, :read_only)
+
+    with {:ok, ref, warnings} <-
+           Native.gdal_open_with_warnings(path, access, open_options, opts[:drivers]),
+         {:ok, ds} <- wrap(ref, path) do
+      {:ok, ds, warnings}
+    end
+  end
+
+  defp open_option(option) when is_binary(option), do: option
+
+  defp open_option(option) do
+    {name, value} = gdal_option(option)
+    "#{name}=#{value}"
+  end
+
+  @doc """
+  Opens a raster dataset from bytes held in memory, without a temp file.
+
+  The bytes are registered as a GDAL `/vsimem/` file named after `file_name`
+  (e.g. `"scene.tif"`), whose extension helps GDAL pick a driver, and are
+  freed when the dataset is closed or garbage collected. The returned
+  dataset's `path` is `nil`.
+  """
+  @spec open_memory(binary(), String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_memory(bytes, file_name) when is_binary(bytes) do
+    with {:ok, ref} <- Native.gdal_open_memory(bytes, file_name) do
+      wrap(ref, nil)
+    end
+  end
+
+  @doc """
+  Opens a remote dataset, such as a Cloud-Optimized GeoTIFF, through GDAL's
+  `/vsicurl/` handler, which fetches only the byte ranges it needs.
+
+  `options` are GDAL config options (e.g. `%{"GDAL_HTTP_HEADERS" => "..."}`)
+  applied while the dataset is opened. They are not in effect for later
+  reads; set options needed for every request process-wide with
+  `set_config_option/2` instead.
+  """
+  @spec open_vsicurl(String.t(), %{optional(String.t()) => String.t()}) ::
+          {:ok, Dataset.t()} | {:error, error()}
+  def open_vsicurl(url, options \\ %{}) do
+    with {:ok, ref} <- Native.gdal_open_vsicurl(url, options) do
+      wrap(ref, url)
+    end
+  end
+
+  @doc """
+  Opens a dataset like `open/1`, but bounds each HTTP request GDAL makes
+  while opening (connecting and transferring) to `timeout_ms` milliseconds,
+  so a stalled server cannot hang the call. Useful for `/vsicurl/` paths.
+
+  The timeout applies only to this open, through thread-local
+  `GDAL_HTTP_TIMEOUT` and `GDAL_HTTP_CONNECTTIMEOUT` config options that
+  are restored afterwards; GDAL has no `CPL_CURL_TIMEOUT` option, these
+  two are the ones it passes to curl. An open that fails because one of
+  its requests timed out returns `{:error, {:timeout, message}}`, with
+  curl's description of the timeout; other failures, such as a 404 or an
+  unrecognized format, keep their usual kind however long they take.
+
+  Local paths are expanded as in `open/1`.
+  """
+  @spec open_timeout(String.t(), pos_integer()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_timeout(path, timeout_ms) do
+    path = expand_local(path)
+
+    with {:ok, ref} <- Native.gdal_open_timeout(path, timeout_ms) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Opens the dataset at `inner_path` inside a zip archive through GDAL's
+  `/vsizip/` handler, without extracting it to disk. `inner_path` is
+  relative to the archive root, e.g. `"rasters/scene.tif"`; see
+  `list_zip/1`.
+
+  The dataset's `path` is the `/vsizip/` connection string. Tar archives
+  open the same way with `open/1` and a `/vsitar/` path.
+  """
+  @spec open_zip(String.t(), String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_zip(archive, inner_path) do
+    archive = Path.expand(archive)
+
+    with {:ok, ref} <- Native.gdal_open_zip(archive, inner_path),
+         {:ok, path} <- Native.gdal_description(ref) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Lists the raster files inside a zip archive, as paths relative to the
+  archive root suitable for `open_zip/2`. Files no raster driver
+  recognises, and directories, are left out.
+
+  Returns `{:error, {:open_failed, _}}` if the archive cannot be read.
   """
+  @spec list_zip(String.t()) :: {:ok, [String.t()]} | {:error, error()}
+  def list_zip(archive) do
+    Native.gdal_list_zip(Path.expand(archive))
+  end
+
+  @doc """
+  Opens a dataset read-only for concurrent reads, such as a tile server
+  reading many windows of one COG.
+
+  A dataset from `open/1` serves one call at a time. This one holds
+  several GDAL handles to the file, one per CPU up to 10, and each call
+  uses whichever handle is free. Opening costs one open per handle.
+
+  The dataset is read-only, so setters and writers return
+  `{:error, {:invalid_argument, _}}`, as does `build_overviews/3`. GDAL
+  3.10's `GDALGetThreadSafeDataset` works the same way, with one handle
+  per thread, but is not in the GDAL versions this library builds
+  against. A band from `band/2` always reads through the first handle, so
+  pass the dataset itself to readers that should run in parallel.
+  """
+  @spec open_threadsafe(String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_threadsafe(path) do
+    path = expand_local(path)
+
+    with {:ok, ref} <- Native.gdal_open_threadsafe(path) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Sets a GDAL config option such as `"GDAL_CACHEMAX"` or
+  `"CPL_VSIL_CURL_ALLOWED_EXTENSIONS"`, or clears it when `value` is `nil`.
 
-  alias ExGdal.{Dataset, GeoTransform, Native}
+  Config options are global: the value applies to every dataset and every
+  BEAM process, including calls already running on other schedulers, so
+  set them once at startup. Some options, such as `GDAL_CACHEMAX`, are only
+  read the first time GDAL needs them.
+  """
+  @spec set_config_option(String.t(), String.t() | nil) :: :ok | {:error, error()}
+  def set_config_option(key, value) do
+    Native.gdal_set_config_option(key, value && to_string(value))
+  end
+
+  @doc """
+  Returns the current value of a GDAL config option (set with
+  `set_config_option/2` or from the environment), or `nil` if unset.
+  """
+  @spec get_config_option(String.t()) :: {:ok, String.t() | nil} | {:error, error()}
+  def get_config_option(key) do
+    Native.gdal_get_config_option(key)
+  end
+
+  @doc """
+  Sets the maximum size in bytes of GDAL's block cache, which holds
+  recently read blocks of every dataset. If the cache already holds more,
+  blocks are flushed until it fits.
+
+  The cache is process-global: one limit is shared by every dataset and
+  every BEAM process, so tune it for the whole node, e.g. larger for tile
+  serving or smaller on memory-constrained nodes.
+  """
+  @spec set_cache_max(non_neg_integer()) :: :ok | {:error, error()}
+  def set_cache_max(bytes) when is_integer(bytes) and bytes >= 0 do
+    Native.gdal_set_cache_max(bytes)
+  end
+
+  @doc """
+  Returns the number of bytes currently held in GDAL's process-global block
+  cache (see `set_cache_max/1`).
+  """
+  @spec get_cache_used() :: {:ok, non_neg_integer()}
+  def get_cache_used, do: {:ok, Native.gdal_get_cache_used()}
+
+  @doc """
+  Creates a new raster dataset with the given driver (e.g. `"GTiff"`).
+
+  All bands share `data_type`, one of `:uint8`, `:int16`, `:uint16`,
+  `:int32`, `:uint32`, `:float32`, `:float64`. Returns an error if the
+  driver does not support direct creation.
+  """
+  @spec create(String.t(), String.t(), pos_integer(), pos_integer(), pos_integer(), atom()) ::
+          {:ok, Dataset.t()} | {:error, error()}
+  def create(driver, path, width, height, band_count, data_type) do
+    path = Path.expand(path)
+
+    with {:ok, ref} <- Native.gdal_create(driver, path, width, height, band_count, data_type) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Creates a new, empty vector dataset with the given driver (e.g.
+  `"GPKG"`), for operations such as `polygonize/4` to add layers to.
+  Returns an error if the driver cannot create vector datasets.
+  """
+  @spec create_vector(String.t(), String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def create_vector(driver, path) do
+    path = Path.expand(path)
+
+    with {:ok, ref} <- Native.gdal_create_vector(driver, path) do
+      wrap(ref, path)
+    end
+  end
+
+  @doc """
+  Writes a copy of the dataset to `path` with another driver (e.g. `"PNG"`
+  or `"COG"`), returning the copy.
+
+  `creation_options` are the driver's creation options as a keyword list or
+  `{name, value}` pairs, e.g. `[compress: "DEFLATE", tiled: true]`. Names
+  are upcased; `true` and `false` become `"YES"` and `"NO"`.
+  """
+  @spec copy(Dataset.t(), String.t(), String.t(), [{atom() | String.t(), term()}]) ::
+          {:ok, Dataset.t()} | {:error, error()}
+  def copy(%Dataset{ref: ref}, driver, path, creation_options \\ []) do
+    path = Path.expand(path)
+    options = Enum.map(creation_options, &gdal_option/1)
+
+    with {:ok, copy} <- Native.gdal_copy(ref, driver, path, options) do
+      wrap(copy, path)
+    end
+  end
+
+  @doc """
+  Writes a copy of the dataset like `copy/4`, sending progress to `pid` so
+  a long copy, such as a large COG, can drive a progress bar.
+
+  `pid` receives `{:progress, fraction}` messages with `fraction` from
+  `0.0` to `1.0`, at most one per percent, then `:done` once the copy
+  succeeds. This holds for drivers that report from worker threads too,
+  such as COG with `num_threads: "ALL_CPUS"`. If the copy fails, no
+  `:done` is sent and the error is returned. The call blocks until the
+  copy completes, so run it in a task when `pid` is the caller's own
+  LiveView.
+  """
+  @spec create_copy_progress(
+          Dataset.t(),
+          String.t(),
+          String.t(),
+          [{atom() | String.t(), term()}],
+          pid()
+        ) :: {:ok, Dataset.t()} | {:error, error()}
+  def create_copy_progress(
+        %Dataset{ref: ref},
+        driver,
+        path,
+        creation_options \\ [],
+        pid \\ self()
+      ) do
+    path = Path.expand(path)
+    options = Enum.map(creation_options, &gdal_option/1)
+
+    with {:ok, copy} <- Native.gdal_create_copy_progress(ref, driver, path, options, pid) do
+      wrap(copy, path)
+    end
+  end
+
+  @doc """
+  Copies the dataset into RAM with GDAL's MEM driver, returning a writable
+  dataset with the same bands, georeferencing and metadata.
+
+  The copy is independent of the source: writes such as `write_band/4` or
+  `fill_nodata/4` change only the copy, and nothing touches disk. Its
+  `path` is `nil`; save it with `copy/4` when needed. The whole raster is
+  held in memory, so size it accordingly.
+  """
+  @spec to_mem(Dataset.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def to_mem(%Dataset{ref: ref}) do
+    with {:ok, copy} <- Native.gdal_to_mem(ref) do
+      wrap(copy, nil)
+    end
+  end
+
+  defp gdal_option({name, value}) do
+    value =
+      case value do
+        true -> "YES"
+        false -> "NO"
+        other -> to_string(other)
+      end
+
+    {name |> to_string() |> String.upcase(), value}
+  end
+
+  # GDAL virtual paths such as "/vsicurl/https://..." are not on the local
+  # filesystem, and expanding one would collapse its "//".
+  defp expand_local("/vsi" <> _ = path), do: path
+  defp expand_local(path), do: Path.expand(path)
+
+  defp wrap(ref, path) do
+    with {:ok, count} <- Native.gdal_raster_count(ref),
+         {:ok, size} <- Native.gdal_raster_size(ref),
+         {:ok, driver} <- Native.gdal_driver_name(ref) do
+      {:ok,
+       %Dataset{
+         ref: ref,
+         path: path,
+         raster_count: count,
+         raster_size: size,
+         driver: driver
+       }}
+    end
+  end
+
+  @doc """
+  Closes the dataset, releasing the GDAL handle and its file descriptors
+  immediately rather than when the reference is garbage collected.
+
+  Closing is idempotent. Any later call on the dataset returns
+  `{:error, {:closed, _}}`; cached struct fields remain readable.
+  """
+  @spec close(Dataset.t()) :: :ok
+  def close(%Dataset{ref: ref}) do
+    Native.gdal_close(ref)
+  end
+
+  @doc "Returns the number of raster bands."
+  @spec band_count(Dataset.t()) :: {:ok, non_neg_integer()} | {:error, error()}
+  def band_count(%Dataset{raster_count: count}), do: {:ok, count}
+
+  @doc "Returns `{width, height}` in pixels."
+  @spec raster_size(Dataset.t()) :: {:ok, {non_neg_integer(), non_neg_integer()}} | {:error, error()}
+  def raster_size(%Dataset{raster_size: size}), do: {:ok, size}
+
+  @doc """
+  Returns the raster size as `%{width: w, height: h}`, the labeled form of
+  `raster_size/1`.
+  """
+  @spec dimensions(Dataset.t()) ::
+          {:ok, %{width: non_neg_integer(), height: non_neg_integer()}} | {:error, error()}
+  def dimensions(%Dataset{ref: ref}) do
+    Native.gdal_dimensions(ref)
+  end
+
+  @doc """
+  Returns a band's own `{width, height}` in pixels. Bands usually match
+  `raster_size/1`, but some formats allow bands of different sizes.
+  """
+  @spec band_size(Dataset.t(), pos_integer()) ::
+          {:ok, {non_neg_integer(), non_neg_integer()}} | {:error, error()}
+  def band_size(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_size(ref, band_idx)
+  end
+
+  @doc """
+  Reads an entire band as a raw binary of native-endian pixels.
+
+  Band index is 1-based.
+  """
+  @spec read_band(Dataset.t(), pos_integer()) :: {:ok, binary()} | {:error, error()}
+  def read_band(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band(ref, band_idx)
+  end
+
+  @doc """
+  Looks up a band once and returns a `%ExGdal.Band{}` for repeated reads
+  with `read_band/1` and `read_band_window/2`.
+  """
+  @spec band(Dataset.t(), pos_integer()) :: {:ok, Band.t()} | {:error, error()}
+  def band(%Dataset{ref: ref} = dataset, band_idx) do
+    with {:ok, band_ref} <- Native.gdal_band(ref, band_idx) do
+      {:ok, %Band{ref: band_ref, dataset: dataset, index: band_idx}}
+    end
+  end
+
+  @doc "Reads an entire band from `band/2`, as `read_band/2` does."
+  @spec read_band(Band.t()) :: {:ok, binary()} | {:error, error()}
+  def read_band(%Band{ref: ref}) do
+    Native.gdal_band_read(ref)
+  end
+
+  @doc """
+  Reads an entire band resampled by GDAL to `width` x `height` pixels, e.g.
+  for a quick preview of a large raster.
+
+  `resampling` is one of `:nearest`, `:bilinear`, `:cubic` or `:average`.
+  Returns native-endian pixels at the band type, like `read_band/2`.
+  """
+  @spec read_band_resampled(Dataset.t(), pos_integer(), pos_integer(), pos_integer(), atom()) ::
+          {:ok, binary()} | {:error, error()}
+  def read_band_resampled(%Dataset{ref: ref}, band_idx, width, height, resampling \\ :nearest) do
+    Native.gdal_read_band_resampled(ref, band_idx, width, height, resampling)
+  end
+
+  @doc """
+  Reads an entire band along with what is needed to build an `Nx` tensor:
+
+      {:ok, %{data: data, type: type, shape: shape}} = ExGdal.read_band_typed(ds, 1)
+      Nx.from_binary(data, type) |> Nx.reshape(shape)
+
+  `type` is the Nx type of the elements (`:u8`, `:s16`, `:u16`, `:s32`,
+  `:u32`, `:f32` or `:f64`), `shape` is `{rows, cols}`, and `endianness` is
+  the byte order of `data`, always the host's native order as Nx expects.
+  """
+  @spec read_band_typed(Dataset.t(), pos_integer()) ::
+          {:ok,
+           %{
+             data: binary(),
+             type: atom(),
+             shape: {non_neg_integer(), non_neg_integer()},
+             endianness: :little | :big
+           }}
+          | {:error, error()}
+  def read_band_typed(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_typed(ref, band_idx)
+  end
+
+  @doc """
+  Reads a band as a list of rows, each a list of Elixir numbers: integers
+  for integer bands, floats for float bands (`nil` for NaN).
+
+  Meant for small rasters, prototyping and tests; bands over 1,048,576
+  pixels return `{:error, {:invalid_argument, _}}`. Use `read_band/2` or
+  `read_band_typed/2` for anything larger.
+  """
+  @spec read_band_rows(Dataset.t(), pos_integer()) ::
+          {:ok, [[integer()]] | [[float() | nil]]} | {:error, error()}
+  def read_band_rows(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_rows(ref, band_idx)
+  end
+
+  @doc """
+  Reads a band as `{type, values}`: the band's data type atom (see
+  `band_type/2`) and a flat, row-major list of Elixir numbers, integers for
+  integer bands and floats for float bands (`nil` for NaN).
+
+  Has the same size limit as `read_band_rows/2`.
+  """
+  @spec read_band_list(Dataset.t(), pos_integer()) ::
+          {:ok, {atom(), [integer()] | [float() | nil]}} | {:error, error()}
+  def read_band_list(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_list(ref, band_idx)
+  end
+
+  @doc """
+  Reads the pixel at `col`, `row` (0-based) as a physical value, `raw *
+  scale + offset` (see `band_scale/2`), or `nil` where it equals the
+  band's nodata value.
+
+  Coordinates outside the band return `{:error, {:invalid_argument, _}}`.
+  """
+  @spec read_pixel(Dataset.t(), pos_integer(), integer(), integer()) ::
+          {:ok, float() | nil} | {:error, error()}
+  def read_pixel(%Dataset{ref: ref}, band_idx, col, row) do
+    Native.gdal_read_pixel(ref, band_idx, col, row)
+  end
+
+  @doc """
+  Reads the physical values at a list of `{col, row}` pixels (0-based), as
+  `read_pixel/4` does, in one call. Each value is `nil` where the pixel is
+  nodata or outside the band.
+  """
+  @spec sample_points(Dataset.t(), pos_integer(), [{integer(), integer()}]) ::
+          {:ok, [float() | nil]} | {:error, error()}
+  def sample_points(%Dataset{ref: ref}, band_idx, points) do
+    Native.gdal_sample_points(ref, band_idx, points)
+  end
+
+  @doc """
+  Reads the physical values at a list of georeferenced `{x, y}` points in
+  the dataset's spatial reference, converting each to pixel coordinates
+  with the inverse geo-transform (see `geo_to_pixel/3`). Each value is
+  `nil` where the point is outside the raster or the value is nodata.
+
+  Options:
+
+    * `:interpolation` - `:nearest` (default) reads the pixel containing
+      the point; `:bilinear` interpolates between the four nearest pixel
+      centres, which suits continuous surfaces such as DEMs. Near the edge
+      the outermost pixels are repeated, and a nodata pixel among the four
+      makes the value `nil`.
+  """
+  @spec sample_coords(Dataset.t(), pos_integer(), [{number(), number()}], keyword()) ::
+          {:ok, [float() | nil]} | {:error, error()}
+  def sample_coords(%Dataset{ref: ref}, band_idx, coords, opts \\ []) do
+    coords = Enum.map(coords, fn {x, y} -> {x / 1, y / 1} end)
+    interpolation = Keyword.get(opts, :interpolation, :nearest)
+    Native.gdal_sample_coords(ref, band_idx, coords, interpolation)
+  end
+
+  @doc """
+  Reads the validity mask of a band as one byte per pixel: `255` where the
+  pixel is valid and `0` where it is not.
+
+  The mask may come from nodata, an alpha band or an explicit mask; see
+  `mask_flags/2`. Bands without any of these read as all `255`.
+  """
+  @spec read_mask_band(Dataset.t(), pos_integer()) :: {:ok, binary()} | {:error, error()}
+  def read_mask_band(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_mask_band(ref, band_idx)
+  end
+
+  @doc """
+  Reports how a band's mask is derived, as a map of booleans:
+
+    * `:all_valid` - there is no mask; every pixel is valid
+    * `:per_dataset` - the mask is shared by all bands
+    * `:alpha` - the mask comes from an alpha band
+    * `:nodata` - the mask comes from the nodata value
+  """
+  @spec mask_flags(Dataset.t(), pos_integer()) ::
+          {:ok, %{all_valid: boolean(), per_dataset: boolean(), alpha: boolean(), nodata: boolean()}}
+          | {:error, error()}
+  def mask_flags(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_mask_flags(ref, band_idx)
+  end
+
+  @doc """
+  Reads several full bands into one binary, interleaved by pixel (BIP).
+
+  For `[1, 2, 3]` the binary holds `r0 g0 b0 r1 g1 b1 ...` in native byte
+  order. Returns `{binary, element_count}` where `element_count` is
+  `width * height * length(band_indices)`. All bands must share a data type.
+  """
+  @spec read_bands(Dataset.t(), [pos_integer()]) ::
+          {:ok, {binary(), non_neg_integer()}} | {:error, error()}
+  def read_bands(%Dataset{ref: ref}, band_indices) do
+    Native.gdal_read_bands(ref, band_indices)
+  end
+
+  @doc """
+  Reads the dataset as tightly-packed 8-bit RGBA, four bytes per pixel.
+
+  The color bands are the ones interpreted as red, green and blue (see
+  `band_color_interp/2`), or bands 1 to 3 when those are missing. Alpha
+  comes from a band interpreted as alpha, otherwise from the red band's
+  validity mask: `0` where invalid and `255` elsewhere. Non-byte bands are
+  clamped to 0..255 by GDAL.
+
+  `window` is `{x, y, width, height}`, or `nil` for the whole raster.
+  """
+  @spec read_rgba(Dataset.t(), window() | nil) :: {:ok, binary()} | {:error, error()}
+  def read_rgba(%Dataset{ref: ref}, window \\ nil) do
+    Native.gdal_read_rgba(ref, window)
+  end
+
+  @doc """
+  Reads an entire band as a raw binary of little-endian pixels, regardless of
+  the host's byte order.
+
+  Band index is 1-based.
+  """
+  @spec read_band_le(Dataset.t(), pos_integer()) :: {:ok, binary()} | {:error, error()}
+  def read_band_le(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_le(ref, band_idx)
+  end
+
+  @doc """
+  Reads an entire band of any data type as little-endian float32 physical
+  values: each pixel is `raw * scale + offset` (see `band_scale/2`), and
+  nodata pixels are NaN. Suits `Nx.from_binary(data, :f32)` on
+  little-endian hosts.
+
+  Band index is 1-based.
+  """
+  @spec read_band_as_float(Dataset.t(), pos_integer()) :: {:ok, binary()} | {:error, error()}
+  def read_band_as_float(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_as_float(ref, band_idx)
+  end
+
+  @doc """
+  Reads an entire band like `read_band_as_float/2`, but leaves nodata
+  pixels as their scaled values and returns validity separately: `mask`
+  holds one byte per pixel, `255` where valid and `0` where not.
+
+  The mask comes from the band's GDAL mask, so it reflects the nodata
+  value, an alpha band or a per-dataset mask (see `mask_flags/2`).
+  """
+  @spec read_band_masked(Dataset.t(), pos_integer()) ::
+          {:ok, %{data: binary(), mask: binary()}} | {:error, error()}
+  def read_band_masked(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_read_band_masked(ref, band_idx)
+  end
+
+  @doc """
+  Returns the byte order used by the native-endian readers such as
+  `read_band/2` and `read_band_window/3`: `:little` or `:big`.
+  """
+  @spec native_endianness() :: {:ok, :little | :big}
+  def native_endianness, do: {:ok, Native.gdal_native_endianness()}
+
+  @doc """
+  Returns the version of the GDAL library the NIF is linked against:
+  `release_name` (e.g. `"3.8.4"`), `version_num` (e.g. `3_080_400`) and
+  `build_info`, a map of build settings such as `"GEOS_ENABLED" => "YES"`.
+  """
+  @spec version() ::
+          {:ok,
+           %{
+             release_name: String.t(),
+             version_num: non_neg_integer(),
+             build_info: %{String.t() => String.t()}
+           }}
+  def version, do: {:ok, Native.gdal_version()}
+
+  @doc """
+  Reads the window `{x, y, w, h}` of a band as a raw binary of
+  native-endian pixels.
+
+  Band index is 1-based. `{x, y}` is the top-left corner and `{w, h}` the
+  window size. Like `read_band/2`, each pixel takes one element of the
+  band's data type.
+  """
+  @spec read_band_window(Dataset.t(), pos_integer(), window()) ::
+          {:ok, binary()} | {:error, error()}
+  def read_band_window(%Dataset{ref: ref}, band_idx, {x, y, w, h}) do
+    Native.gdal_read_band_window(ref, band_idx, x, y, w, h)
+  end
+
+  @doc "Reads a window of a band from `band/2`, as `read_band_window/3` does."
+  @spec read_band_window(Band.t(), window()) :: {:ok, binary()} | {:error, error()}
+  def read_band_window(%Band{ref: ref}, window) do
+    Native.gdal_band_read_window(ref, window)
+  end
+
+  @doc """
+  Reads a window of a band given as separate `x`, `y`, `w` and `h`
+  arguments. Kept for compatibility; same as `read_band_window/3`.
+  """
+  @spec read_band_window(Dataset.t(), pos_integer(), integer(), integer(), non_neg_integer(), non_neg_integer()) ::
+          {:ok, binary()} | {:error, error()}
+  def read_band_window(%Dataset{} = ds, band_idx, x, y, w, h) do
+    read_band_window(ds, band_idx, {x, y, w, h})
+  end
+
+  @doc """
+  Reads several `{x, y, w, h}` windows of a band in one call, returning
+  their binaries in the same order, as `read_band_window/3` would.
+
+  Stops at the first window that is outside the band or fails to read,
+  returning an error whose message names that window's 0-based index.
+  """
+  @spec read_windows(Dataset.t(), pos_integer(), [window()]) ::
+          {:ok, [binary()]} | {:error, error()}
+  def read_windows(%Dataset{ref: ref}, band_idx, windows) do
+    Native.gdal_read_windows(ref, band_idx, windows)
+  end
+
+  @doc """
+  Reads the window `{x, y, w, h}` of a band resampled by GDAL to an
+  `{out_w, out_h}` pixel output, e.g. a geographic window rendered as a
+  256x256 map tile.
+
+  `resampling` is one of `:nearest`, `:bilinear`, `:cubic` or `:average`.
+  Returns native-endian pixels at the band type, like `read_band_window/3`.
+  Like `raster_io/5`, it reads through the band's handle from `band/2`.
+  """
+  @spec read_band_window_resampled(
+          Dataset.t(),
+          pos_integer(),
+          window(),
+          {pos_integer(), pos_integer()},
+          atom()
+        ) :: {:ok, binary()} | {:error, error()}
+  def read_band_window_resampled(
+        %Dataset{} = ds,
+        band_idx,
+        window,
+        out_size,
+        resampling \\ :nearest
+      ) do
+    {x, y, w, h} = window
+    {out_w, out_h} = out_size
+
+    with {:ok, %Band{ref: band}} <- band(ds, band_idx) do
+      Native.gdal_read_band_window_resampled(band, x, y, w, h, out_w, out_h, resampling)
+    end
+  end
+
+  @doc """
+  Low-level `RasterIO` read of the window `{x, y, w, h}` into an
+  `{out_w, out_h}` buffer laid out with explicit `{pixel_space, line_space}`
+  byte strides, e.g. `{3, 3 * out_w}` to leave room for interleaving two
+  more single-byte bands.
+
+  A stride of `0` takes GDAL's default: the band type's size for
+  `pixel_space` and `pixel_space * out_w` for `line_space`. Pixels are in
+  the band type, native-endian; bytes between them are zero. Strides that
+  would overlap pixels, or a buffer too large to address, return
+  `{:error, {:invalid_argument, _}}`.
+
+  The window, output size and strides are tuples, as for the other
+  windowed readers, so the pairs cannot be passed in the wrong order. The
+  read goes through the band's handle from `band/2`, so on an
+  `open_threadsafe/1` dataset it always uses the first handle.
+  """
+  @spec raster_io(
+          Dataset.t(),
+          pos_integer(),
+          window(),
+          {non_neg_integer(), non_neg_integer()},
+          {non_neg_integer(), non_neg_integer()}
+        ) :: {:ok, binary()} | {:error, error()}
+  def raster_io(%Dataset{} = ds, band_idx, window, out_size, spacing \\ {0, 0}) do
+    {x, y, w, h} = window
+    {out_w, out_h} = out_size
+    {pixel_space, line_space} = spacing
+
+    with {:ok, %Band{ref: band}} <- band(ds, band_idx) do
+      Native.gdal_raster_io(band, x, y, w, h, out_w, out_h, pixel_space, line_space)
+    end
+  end
+
+  @doc """
+  Returns the natural `{block_x, block_y}` size of a band: the tile size for
+  tiled formats, or the strip size (full width by rows per strip) otherwise.
+
+  Windowed reads aligned to this grid avoid touching neighbouring blocks.
+  """
+  @spec block_size(Dataset.t(), pos_integer()) ::
+          {:ok, {pos_integer(), pos_integer()}} | {:error, error()}
+  def block_size(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_block_size(ref, band_idx)
+  end
+
+  @doc """
+  Reads a single native block as a raw binary of native-endian pixels.
+
+  `block_x` and `block_y` are 0-based block column and row (see `block_size/2`).
+  The binary always covers a full block; edge blocks are padded by GDAL.
+  """
+  @spec read_block(Dataset.t(), pos_integer(), non_neg_integer(), non_neg_integer()) ::
+          {:ok, binary()} | {:error, error()}
+  def read_block(%Dataset{ref: ref}, band_idx, block_x, block_y) do
+    Native.gdal_read_block(ref, band_idx, block_x, block_y)
+  end
+
+  @doc """
+  Streams every block of a band to `pid` without materializing the band.
+
+  Returns `:ok` immediately; a background thread then sends one
+  `{:block, block_x, block_y, binary}` message per block, in row-major
+  order, followed by `:done`. Binaries are as from `read_block/4`. On
+  failure, including an invalid band, `{:error, {kind, message}}` is sent
+  instead of `:done`. Messages are sent as fast as blocks are read, with no
+  backpressure; streaming stops early if `pid` exits.
+  """
+  @spec stream_blocks(Dataset.t(), pos_integer(), pid()) :: :ok
+  def stream_blocks(%Dataset{ref: ref}, band_idx, pid \\ self()) do
+    Native.gdal_stream_blocks(ref, band_idx, pid)
+  end
+
+  @doc "Returns the number of overviews (reduced-resolution pyramids) of a band."
+  @spec overview_count(Dataset.t(), pos_integer()) :: {:ok, non_neg_integer()} | {:error, error()}
+  def overview_count(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_overview_count(ref, band_idx)
+  end
+
+  @doc """
+  Returns the `{width, height}` of each overview of a band, in overview index
+  order (the largest level first), e.g. to pick the smallest overview that
+  still covers a target output size before calling `read_overview/3`.
+  """
+  @spec overview_sizes(Dataset.t(), pos_integer()) ::
+          {:ok, [{pos_integer(), pos_integer()}]} | {:error, error()}
+  def overview_sizes(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_overview_sizes(ref, band_idx)
+  end
+
+  @doc """
+  Builds overviews for every band, one per decimation factor (e.g. `[2, 4, 8]`).
+
+  `resampling` is one of `:nearest`, `:average`, `:gauss`, `:cubic`, `:lanczos`.
+  Formats that cannot store internal overviews get an external `.ovr` file.
+  """
+  @spec build_overviews(Dataset.t(), atom(), [pos_integer()]) :: :ok | {:error, error()}
+  def build_overviews(%Dataset{ref: ref}, resampling, factors) do
+    Native.gdal_build_overviews(ref, resampling, factors)
+  end
+
+  @doc """
+  Reads an entire overview level of a band as a raw binary of native-endian pixels.
+
+  Band index is 1-based; overview index is 0-based, from the largest overview
+  to the smallest.
+  """
+  @spec read_overview(Dataset.t(), pos_integer(), non_neg_integer()) :: {:ok, binary()} | {:error, error()}
+  def read_overview(%Dataset{ref: ref}, band_idx, overview_idx) do
+    Native.gdal_read_overview(ref, band_idx, overview_idx)
+  end
+
+  @doc """
+  Reads a window `{x, y, width, height}` of full-resolution pixels at
+  `{out_width, out_height}`, from the cheapest source that still has the
+  resolution: the smallest overview covering the window with at least as
+  many pixels as the output, or the full-resolution band when none does.
+  This is the usual tile read of a Cloud-Optimized GeoTIFF in one call.
+
+  `resampling` is as for `read_band_window_resampled/5`. Returns
+  native-endian pixels at the band type.
+  """
+  @spec read_for_resolution(
+          Dataset.t(),
+          pos_integer(),
+          window(),
+          {pos_integer(), pos_integer()},
+          atom()
+        ) :: {:ok, binary()} | {:error, error()}
+  def read_for_resolution(
+        %Dataset{ref: ref},
+        band_idx,
+        window,
+        out_size,
+        resampling \\ :nearest
+      ) do
+    Native.gdal_read_for_resolution(ref, band_idx, window, out_size, resampling)
+  end
+
+  @doc """
+  Writes an entire band from a raw binary of native-endian pixels.
+
+  `data_type` describes the layout of `data` (see `create/6`) and need not
+  match the band's own type; GDAL converts on write. The binary must hold
+  exactly `width * height` elements.
+  """
+  @spec write_band(Dataset.t(), pos_integer(), binary(), atom()) :: :ok | {:error, error()}
+  def write_band(%Dataset{ref: ref}, band_idx, data, data_type) do
+    Native.gdal_write_band(ref, band_idx, data, data_type)
+  end
+
+  @doc """
+  Flushes pending writes to disk without closing the dataset.
+  """
+  @spec flush_cache(Dataset.t()) :: :ok | {:error, error()}
+  def flush_cache(%Dataset{ref: ref}) do
+    Native.gdal_flush_cache(ref)
+  end
+
+  @doc """
+  Returns the data type of a band as an atom.
+
+  Possible values: `:uint8`, `:int16`, `:uint16`, `:int32`, `:uint32`,
+  `:float32`, `:float64`, `:unknown`.
+  """
+  @spec band_type(Dataset.t(), pos_integer()) :: {:ok, atom()} | {:error, error()}
+  def band_type(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_type(ref, band_idx)
+  end
+
+  @doc """
+  Returns the data type of the bytes the native-endian readers such as
+  `read_band/2` return for a band, with what is needed to decode them: the
+  element `size` in bytes, whether it is `signed` (floats are) and the host
+  `endianness`.
+
+      {:ok, %{type: :int16, size: 2, signed: true, endianness: :little}}
+
+  This is the band's own type except for types the readers convert:
+  Int8, Int64, UInt64 and complex bands read as `:float64`, while
+  `band_type/2` reports them as `:unknown`.
+  """
+  @spec band_type_info(Dataset.t(), pos_integer()) ::
+          {:ok,
+           %{type: atom(), size: pos_integer(), signed: boolean(), endianness: :little | :big}}
+          | {:error, error()}
+  def band_type_info(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_type_info(ref, band_idx)
+  end
+
+  @doc """
+  Returns the Nx type of a band's elements as written in Elixir, such as
+  `"{:f, 32}"` or `"{:s, 16}"`, for generated code and logs.
+
+  It is the type of the bytes `read_band/2`, `read_band_window/3` and the
+  other native-endian readers return, matching `read_band_typed/2`'s
+  `:type`: bands Nx has no type for, such as complex bands, read as
+  `"{:f, 64}"`.
+  """
+  @spec nx_type(Dataset.t(), pos_integer()) :: {:ok, String.t()} | {:error, error()}
+  def nx_type(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_nx_type(ref, band_idx)
+  end
+
+  @doc """
+  Returns the size in bytes of one element of a data type atom (see
+  `create/6`), as GDAL's `GDALGetDataTypeSizeBytes`: `:float32` is `4`.
+
+  Unsupported atoms, including `:unknown`, return
+  `{:error, {:invalid_argument, _}}`.
+  """
+  @spec data_type_size(atom()) :: {:ok, pos_integer()} | {:error, error()}
+  def data_type_size(data_type) do
+    Native.gdal_data_type_size(data_type)
+  end
+
+  @doc """
+  Returns the color interpretation of a band as an atom.
+
+  Common values: `:red`, `:green`, `:blue`, `:alpha`, `:gray`, `:palette`,
+  `:undefined`. HLS, CMYK and YCbCr bands report `:hue`, `:saturation`,
+  `:lightness`, `:cyan`, `:magenta`, `:yellow`, `:black`, `:ycbcr_y`,
+  `:ycbcr_cb`, `:ycbcr_cr`.
+  """
+  @spec band_color_interp(Dataset.t(), pos_integer()) :: {:ok, atom()} | {:error, error()}
+  def band_color_interp(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_color_interp(ref, band_idx)
+  end
+
+  @doc "Sets the color interpretation of a band. Accepts the atoms returned by `band_color_interp/2`."
+  @spec set_band_color_interp(Dataset.t(), pos_integer(), atom()) :: :ok | {:error, error()}
+  def set_band_color_interp(%Dataset{ref: ref}, band_idx, interp) do
+    Native.gdal_set_band_color_interp(ref, band_idx, interp)
+  end
+
+  @doc """
+  Returns whether the dataset is paletted: band 1 is interpreted as
+  `:palette`, so its pixels index `color_table/2` rather than hold colors.
+  Datasets without raster bands return `{:ok, false}`.
+  """
+  @spec is_paletted(Dataset.t()) :: {:ok, boolean()} | {:error, error()}
+  def is_paletted(%Dataset{ref: ref}) do
+    Native.gdal_is_paletted(ref)
+  end
+
+  @doc """
+  Returns the 1-based index of the first band interpreted as `:alpha` (see
+  `band_color_interp/2`), or `{:ok, nil}` if the dataset has no alpha band.
+  """
+  @spec has_alpha(Dataset.t()) :: {:ok, pos_integer() | nil} | {:error, error()}
+  def has_alpha(%Dataset{ref: ref}) do
+    Native.gdal_has_alpha(ref)
+  end
+
+  @doc """
+  Returns the color table of a paletted band as a list of `{r, g, b, a}`
+  tuples of 0..255 values, indexed by pixel value.
+
+  Returns `{:ok, nil}` if the band has no color table.
+  """
+  @spec color_table(Dataset.t(), pos_integer()) ::
+          {:ok, [{0..255, 0..255, 0..255, 0..255}] | nil} | {:error, error()}
+  def color_table(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_color_table(ref, band_idx)
+  end
+
+  @doc "Returns the no-data value for a band, or `{:ok, nil}` if not set."
+  @spec no_data_value(Dataset.t(), pos_integer()) :: {:ok, float() | nil} | {:error, error()}
+  def no_data_value(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_no_data_value(ref, band_idx)
+  end
+
+  @doc """
+  Sets the no-data value for a band, so GDAL and downstream tools skip pixels
+  holding `value`.
+  """
+  @spec set_no_data_value(Dataset.t(), pos_integer(), number()) :: :ok | {:error, error()}
+  def set_no_data_value(%Dataset{ref: ref}, band_idx, value) do
+    Native.gdal_set_no_data_value(ref, band_idx, value / 1)
+  end
+
+  @doc "Removes the no-data value from a band."
+  @spec delete_no_data_value(Dataset.t(), pos_integer()) :: :ok | {:error, error()}
+  def delete_no_data_value(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_delete_no_data_value(ref, band_idx)
+  end
+
+  @doc """
+  Computes GDAL's checksum of a band, or of a `{x, y, w, h}` window of it,
+  as a cheap fingerprint for change detection.
+
+  Floating-point pixels are truncated to integers first, so changes in the
+  fractional part do not change the checksum.
+  """
+  @spec band_checksum(Dataset.t(), pos_integer(), window() | nil) ::
+          {:ok, non_neg_integer()} | {:error, error()}
+  def band_checksum(%Dataset{ref: ref}, band_idx, window \\ nil) do
+    Native.gdal_band_checksum(ref, band_idx, window)
+  end
+
+  @doc """
+  Computes `%{min: _, max: _, mean: _, std_dev: _}` for a band, excluding nodata.
+
+  With `approx` set to `true`, the statistics come from the smallest
+  overview with at least 2500 pixels, which is much faster on large
+  rasters; bands without overviews are read in full. Returns
+  `{:error, {:all_nodata, _}}` if the band has no valid pixels.
+
+  The statistics are not stored, so no `.aux.xml` sidecar is written.
+  """
+  @spec band_statistics(Dataset.t(), pos_integer(), boolean()) ::
+          {:ok, %{min: float(), max: float(), mean: float(), std_dev: float()}}
+          | {:error, error()}
+  def band_statistics(%Dataset{ref: ref}, band_idx, approx \\ false) do
+    Native.gdal_band_statistics(ref, band_idx, approx)
+  end
 
   @doc """
-  Opens a raster dataset at the given path.
+  Computes `{min, max}` for a band without the cost of full statistics.
 
-  Returns `{:ok, %Dataset{}}` with cached metadata, or `{:error, reason}`.
+  Pixels equal to the nodata value are excluded, so the range covers real
+  data only; a band where every pixel is nodata returns
+  `{:error, {:all_nodata, _}}`. With `approx: true` GDAL trusts stored
+  min/max values or samples overviews instead of reading every pixel.
   """
-  @spec open(String.t()) :: {:ok, Dataset.t()} | {:error, String.t()}
-  def open(path) do
-    path = Path.expand(path)
-
-    with {:ok, ref} <- Native.gdal_open(path),
-         {:ok, count} <- Native.gdal_raster_count(ref),
-         {:ok, size} <- Native.gdal_raster_size(ref),
-         {:ok, driver} <- Native.gdal_driver_name(ref) do
-      {:ok,
-       %Dataset{
-         ref: ref,
-         path: path,
-         raster_count: count,
-         raster_size: size,
-         driver: driver
-       }}
-    end
+  @spec band_min_max(Dataset.t(), pos_integer(), boolean()) :: {:ok, {float(), float()}} | {:error, error()}
+  def band_min_max(%Dataset{ref: ref}, band_idx, approx \\ false) do
+    Native.gdal_band_min_max(ref, band_idx, approx)
   end
 
-  @doc "Returns the number of raster bands."
-  @spec band_count(Dataset.t()) :: {:ok, non_neg_integer()} | {:error, String.t()}
-  def band_count(%Dataset{raster_count: count}), do: {:ok, count}
+  @doc """
+  Computes a histogram of `buckets` equal-width buckets spanning `[min, max]`.
 
-  @doc "Returns `{width, height}` in pixels."
-  @spec raster_size(Dataset.t()) :: {:ok, {non_neg_integer(), non_neg_integer()}} | {:error, String.t()}
-  def raster_size(%Dataset{raster_size: size}), do: {:ok, size}
+  Returns the per-bucket pixel counts. With `include_out_of_range: true`,
+  values below `min` or above `max` are counted in the first and last bucket
+  instead of being discarded. Nodata pixels are never counted.
+  """
+  @spec band_histogram(Dataset.t(), pos_integer(), number(), number(), pos_integer(), boolean()) ::
+          {:ok, [non_neg_integer()]} | {:error, error()}
+  def band_histogram(%Dataset{ref: ref}, band_idx, min, max, buckets, include_out_of_range \\ false) do
+    Native.gdal_band_histogram(ref, band_idx, min / 1, max / 1, buckets, include_out_of_range)
+  end
 
   @doc """
-  Reads an entire band as a raw binary of native-endian pixels.
+  Returns the scale applied to raw band values: `physical = raw * scale + offset`.
 
-  Band index is 1-based.
+  Defaults to `1.0` when the band does not define one.
   """
-  @spec read_band(Dataset.t(), pos_integer()) :: {:ok, binary()} | {:error, String.t()}
-  def read_band(%Dataset{ref: ref}, band_idx) do
-    Native.gdal_read_band(ref, band_idx)
+  @spec band_scale(Dataset.t(), pos_integer()) :: {:ok, float()} | {:error, error()}
+  def band_scale(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_scale(ref, band_idx)
   end
 
   @doc """
-  Reads a rectangular window from a band as raw bytes.
+  Returns the offset applied to raw band values after scaling (see `band_scale/2`).
 
-  Band index is 1-based. (x, y) is the top-left corner, (w, h) is the window size.
+  Defaults to `0.0` when the band does not define one.
   """
-  @spec read_band_window(Dataset.t(), pos_integer(), integer(), integer(), non_neg_integer(), non_neg_integer()) ::
-          {:ok, binary()} | {:error, String.t()}
-  def read_band_window(%Dataset{ref: ref}, band_idx, x, y, w, h) do
-    Native.gdal_read_band_window(ref, band_idx, x, y, w, h)
+  @spec band_offset(Dataset.t(), pos_integer()) :: {:ok, float()} | {:error, error()}
+  def band_offset(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_offset(ref, band_idx)
   end
 
   @doc """
-  Returns the data type of a band as an atom.
+  Returns the unit of a band's physical values (e.g. `"m"`, `"Kelvin"`).
 
-  Possible values: `:uint8`, `:int16`, `:uint16`, `:int32`, `:uint32`,
-  `:float32`, `:float64`, `:unknown`.
+  An empty string means the unit is unspecified.
   """
-  @spec band_type(Dataset.t(), pos_integer()) :: {:ok, atom()} | {:error, String.t()}
-  def band_type(%Dataset{ref: ref}, band_idx) do
-    Native.gdal_band_type(ref, band_idx)
+  @spec band_unit_type(Dataset.t(), pos_integer()) :: {:ok, String.t()} | {:error, error()}
+  def band_unit_type(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_unit_type(ref, band_idx)
   end
 
-  @doc "Returns the no-data value for a band, or `{:ok, nil}` if not set."
-  @spec no_data_value(Dataset.t(), pos_integer()) :: {:ok, float() | nil} | {:error, String.t()}
-  def no_data_value(%Dataset{ref: ref}, band_idx) do
-    Native.gdal_no_data_value(ref, band_idx)
+  @doc """
+  Returns the category names (class labels) of a classified band, where
+  the name at position `i` labels pixel value `i`. Returns an empty list
+  when the band has no categories.
+  """
+  @spec band_category_names(Dataset.t(), pos_integer()) ::
+          {:ok, [String.t()]} | {:error, error()}
+  def band_category_names(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_category_names(ref, band_idx)
+  end
+
+  @doc """
+  Returns a band's raster attribute table, or `nil` if it has none.
+
+  Each column is `%{name: _, type: _, usage: _}`, with `type` one of
+  `:integer`, `:real` or `:string` and `usage` GDAL's column role, such as
+  `:generic`, `:pixel_count`, `:name`, `:min_max` or `:red`. Each row is a
+  list of values in column order.
+  """
+  @spec band_rat(Dataset.t(), pos_integer()) ::
+          {:ok,
+           %{
+             columns: [%{name: String.t(), type: :integer | :real | :string, usage: atom()}],
+             rows: [[integer() | float() | String.t()]]
+           }
+           | nil}
+          | {:error, error()}
+  def band_rat(%Dataset{ref: ref}, band_idx) do
+    Native.gdal_band_rat(ref, band_idx)
   end
 
   @doc "Returns the spatial reference as WKT."
-  @spec spatial_ref_wkt(Dataset.t()) :: {:ok, String.t()} | {:error, String.t()}
+  @spec spatial_ref_wkt(Dataset.t()) :: {:ok, String.t()} | {:error, error()}
   def spatial_ref_wkt(%Dataset{ref: ref}) do
     Native.gdal_spatial_ref_wkt(ref)
   end
 
   @doc "Returns the spatial reference as PROJ4 string."
-  @spec spatial_ref_proj4(Dataset.t()) :: {:ok, String.t()} | {:error, String.t()}
+  @spec spatial_ref_proj4(Dataset.t()) :: {:ok, String.t()} | {:error, error()}
   def spatial_ref_proj4(%Dataset{ref: ref}) do
     Native.gdal_spatial_ref_proj4(ref)
   end
 
+  @doc """
+  Returns the EPSG code of the dataset's spatial reference, e.g. `4326`.
+
+  Definitions without a recorded authority are matched against well-known
+  EPSG systems; `{:ok, nil}` means no match was found. Returns
+  `{:error, {:no_spatial_ref, _}}` if the dataset has no spatial reference.
+  """
+  @spec spatial_ref_epsg(Dataset.t()) :: {:ok, integer() | nil} | {:error, error()}
+  def spatial_ref_epsg(%Dataset{ref: ref}) do
+    Native.gdal_spatial_ref_epsg(ref)
+  end
+
+  @doc """
+  Returns the number of ground control points, which georeference some
+  datasets (such as raw satellite scenes) instead of a geo-transform.
+  """
+  @spec gcp_count(Dataset.t()) :: {:ok, non_neg_integer()} | {:error, error()}
+  def gcp_count(%Dataset{ref: ref}) do
+    Native.gdal_gcp_count(ref)
+  end
+
+  @doc """
+  Returns the dataset's ground control points as maps mapping a
+  `pixel`/`line` position to `x`/`y`/`z` in the GCP spatial reference
+  (see `gcp_spatial_ref_wkt/1`). `z` is `0.0` when unknown.
+  """
+  @spec gcps(Dataset.t()) ::
+          {:ok,
+           [
+             %{
+               id: String.t(),
+               pixel: float(),
+               line: float(),
+               x: float(),
+               y: float(),
+               z: float()
+             }
+           ]}
+          | {:error, error()}
+  def gcps(%Dataset{ref: ref}) do
+    Native.gdal_gcps(ref)
+  end
+
+  @doc """
+  Returns the spatial reference of the ground control points as WKT, or
+  `{:error, {:no_spatial_ref, _}}` if the dataset has none.
+  """
+  @spec gcp_spatial_ref_wkt(Dataset.t()) :: {:ok, String.t()} | {:error, error()}
+  def gcp_spatial_ref_wkt(%Dataset{ref: ref}) do
+    Native.gdal_gcp_spatial_ref_wkt(ref)
+  end
+
+  @doc """
+  Reprojects a list of `{x, y}` points from `source` to `target`.
+
+  Each spatial reference is an EPSG code (`4326`) or any definition GDAL
+  accepts: WKT, a PROJ string, or `"EPSG:3857"`. Coordinates are always in
+  longitude/latitude (easting/northing) order. Returns
+  `{:error, {:transform_failed, _}}` if PROJ cannot transform the points.
+  """
+  @spec transform_coords(srs, srs, [{number(), number()}]) ::
+          {:ok, [{float(), float()}]} | {:error, error()}
+        when srs: pos_integer() | String.t()
+  def transform_coords(source, target, points) do
+    Native.gdal_transform_coords(source, target, to_float_points(points))
+  end
+
+  @doc """
+  Builds a reusable transform from `source` to `target` (spatial references
+  as for `transform_coords/3`) for `transform_with/2`. Prefer this over
+  `transform_coords/3` when reprojecting many batches of points.
+  """
+  @spec create_coord_transform(srs, srs) :: {:ok, CoordTransform.t()} | {:error, error()}
+        when srs: pos_integer() | String.t()
+  def create_coord_transform(source, target) do
+    with {:ok, ref} <- Native.gdal_create_coord_transform(source, target) do
+      {:ok, %CoordTransform{ref: ref, source: source, target: target}}
+    end
+  end
+
+  @doc """
+  Reprojects a list of `{x, y}` points with a transform from
+  `create_coord_transform/2`, with the same axis order and errors as
+  `transform_coords/3`.
+  """
+  @spec transform_with(CoordTransform.t(), [{number(), number()}]) ::
+          {:ok, [{float(), float()}]} | {:error, error()}
+  def transform_with(%CoordTransform{ref: ref}, points) do
+    Native.gdal_transform_with(ref, to_float_points(points))
+  end
+
+  defp to_float_points(points), do: Enum.map(points, fn {x, y} -> {x / 1, y / 1} end)
+
+  @doc """
+  Reprojects the dataset into `srs` (an EPSG code or any definition GDAL
+  accepts), returning the warped copy as a new dataset.
+
+  Options:
+
+    * `:resolution` - output pixel size as `{x_res, y_res}` in target
+      units; by default GDAL picks one close to the source's
+    * `:resampling` - `:nearest` (default), `:bilinear`, `:cubic`,
+      `:lanczos` or `:average`
+    * `:path` - write the result to this file instead of memory
+    * `:driver` - output driver when `:path` is given, default `"GTiff"`
+  """
+  @spec warp(Dataset.t(), pos_integer() | String.t(), keyword()) ::
+          {:ok, Dataset.t()} | {:error, error()}
+  def warp(%Dataset{ref: ref}, srs, opts \\ []) do
+    resolution =
+      case Keyword.get(opts, :resolution) do
+        {x_res, y_res} -> {x_res / 1, y_res / 1}
+        nil -> nil
+      end
+
+    resampling = Keyword.get(opts, :resampling, :nearest)
+    path = opts[:path] && Path.expand(opts[:path])
+    output = if path, do: {Keyword.get(opts, :driver, "GTiff"), path}
+
+    with {:ok, warped} <- Native.gdal_warp(ref, srs, resolution, resampling, output) do
+      wrap(warped, path)
+    end
+  end
+
+  @doc """
+  Converts the dataset into a new one, optionally subsetting bands and
+  pixels and changing the data type, as the `gdal_translate` utility does.
+
+  Options:
+
+    * `:bands` - 1-based band indices to keep, in order; default all
+    * `:type` - output data type, e.g. `:uint8` (see `create/6`)
+    * `:scale` - `true` to scale the source min/max to 0..255, or
+      `{src_min, src_max, dst_min, dst_max}`
+    * `:window` - pixel window `{x, y, width, height}` to extract
+    * `:path` - write the result to this file instead of memory
+    * `:driver` - output driver when `:path` is given, default `"GTiff"`
+  """
+  @spec translate(Dataset.t(), keyword()) :: {:ok, Dataset.t()} | {:error, error()}
+  def translate(%Dataset{ref: ref}, opts \\ []) do
+    scale =
+      case Keyword.get(opts, :scale) do
+        {src_min, src_max, dst_min, dst_max} ->
+          {src_min / 1, src_max / 1, dst_min / 1, dst_max / 1}
+
+        other ->
+          other
+      end
+
+    path = opts[:path] && Path.expand(opts[:path])
+    output = if path, do: {Keyword.get(opts, :driver, "GTiff"), path}
+    bands = Keyword.get(opts, :bands, [])
+
+    with {:ok, translated} <-
+           Native.gdal_translate(ref, bands, opts[:type], scale, opts[:window], output) do
+      wrap(translated, path)
+    end
+  end
+
+  @doc """
+  Encodes a pixel window as a PNG or JPEG image and returns its bytes, e.g.
+  to serve as a map tile. The window `{x, y, width, height}` is resampled
+  to `{out_width, out_height}`; `format` is `:png` or `:jpeg`.
+
+  The image is written through `/vsimem`, never touching disk. PNG takes
+  `:uint8` or `:uint16` bands and JPEG `:uint8`; use `translate/2` with
+  `:type` and `:scale` to convert other data first.
+
+  Options:
+
+    * `:quality` - JPEG quality from `1` to `100` (GDAL default `75`);
+      `:jpeg` only
+  """
+  @spec encode_window(
+          Dataset.t(),
+          {integer(), integer(), pos_integer(), pos_integer()},
+          {pos_integer(), pos_integer()},
+          :png | :jpeg,
+          keyword()
+        ) :: {:ok, binary()} | {:error, error()}
+  def encode_window(%Dataset{ref: ref}, window, out_size, format, opts \\ []) do
+    Native.gdal_encode_window(ref, window, out_size, format, opts[:quality])
+  end
+
+  @doc """
+  Computes a terrain derivative of a DEM band into a new dataset at `path`,
+  as the `gdaldem` utility does. `mode` is one of `:hillshade`, `:slope`,
+  `:aspect`, `:roughness`, `:tri` (terrain ruggedness index) or `:tpi`
+  (topographic position index).
+
+  Options:
+
+    * `:band` - 1-based DEM band, default `1`
+    * `:z_factor` - vertical exaggeration applied to elevations
+    * `:scale` - ratio of horizontal to vertical units, e.g. `111_120` for
+      elevations in metres on a geographic (degree) grid
+    * `:azimuth` - light direction in degrees clockwise from north,
+      `:hillshade` only (GDAL default `315`)
+    * `:altitude` - light elevation in degrees above the horizon,
+      `:hillshade` only (GDAL default `45`)
+    * `:driver` - output driver, default `"GTiff"`
+  """
+  @spec dem_processing(Dataset.t(), atom(), Path.t(), keyword()) ::
+          {:ok, Dataset.t()} | {:error, error()}
+  def dem_processing(%Dataset{ref: ref}, mode, path, opts \\ []) do
+    path = Path.expand(path)
+    output = {Keyword.get(opts, :driver, "GTiff"), path}
+    band_idx = Keyword.get(opts, :band, 1)
+
+    [z_factor, scale, azimuth, altitude] =
+      for key <- [:z_factor, :scale, :azimuth, :altitude], do: opts[key] && opts[key] / 1
+
+    light = {azimuth, altitude}
+
+    with {:ok, processed} <-
+           Native.gdal_dem_processing(ref, band_idx, mode, output, z_factor, scale, light) do
+      wrap(processed, path)
+    end
+  end
+
+  @doc """
+  Assigns a spatial reference to the dataset, given as an EPSG code (`4326`)
+  or any definition GDAL accepts: WKT, a PROJ string, or `"EPSG:3857"`.
+
+  Returns `{:error, {:invalid_argument, _}}` if the definition does not parse.
+  """
+  @spec set_spatial_ref(Dataset.t(), pos_integer() | String.t()) :: :ok | {:error, error()}
+  def set_spatial_ref(%Dataset{ref: ref}, definition) do
+    Native.gdal_set_spatial_ref(ref, definition)
+  end
+
   @doc "Returns the geo-transform as a `%GeoTransform{}` struct."
-  @spec geo_transform(Dataset.t()) :: {:ok, GeoTransform.t()} | {:error, String.t()}
+  @spec geo_transform(Dataset.t()) :: {:ok, GeoTransform.t()} | {:error, error()}
   def geo_transform(%Dataset{ref: ref}) do
     case Native.gdal_geo_transform(ref) do
       {:ok, list} when is_list(list) and length(list) == 6 ->
@@ -101,20 +1406,92 @@ defmodule ExGdal do
     end
   end
 
+  @doc """
+  Returns the geo-transform as a plain map under GDAL's coefficient names:
+  `origin_x`, `pixel_width`, `row_rotation`, `origin_y`, `col_rotation` and
+  `pixel_height`. The rotations are the `skew_x` and `skew_y` fields of
+  `%GeoTransform{}`.
+  """
+  @spec geo_transform_map(Dataset.t()) ::
+          {:ok,
+           %{
+             origin_x: float(),
+             pixel_width: float(),
+             row_rotation: float(),
+             origin_y: float(),
+             col_rotation: float(),
+             pixel_height: float()
+           }}
+          | {:error, error()}
+  def geo_transform_map(%Dataset{ref: ref}) do
+    Native.gdal_geo_transform_map(ref)
+  end
+
+  @doc """
+  Sets the dataset's geo-transform from a `%GeoTransform{}` or a list of the
+  six GDAL coefficients `[origin_x, pixel_width, skew_x, origin_y, skew_y,
+  pixel_height]`. Any other list length returns
+  `{:error, {:invalid_argument, _}}`.
+  """
+  @spec set_geo_transform(Dataset.t(), GeoTransform.t() | [number()]) :: :ok | {:error, error()}
+  def set_geo_transform(%Dataset{} = ds, %GeoTransform{} = gt) do
+    set_geo_transform(ds, GeoTransform.to_list(gt))
+  end
+
+  def set_geo_transform(%Dataset{ref: ref}, coefficients) when is_list(coefficients) do
+    Native.gdal_set_geo_transform(ref, Enum.map(coefficients, &(&1 / 1)))
+  end
+
+  @doc """
+  Converts a pixel/line coordinate to georeferenced `{x, y}` using the
+  dataset's geo-transform. Fractional coordinates are allowed; `{0, 0}` is the
+  top-left corner of the top-left pixel.
+  """
+  @spec pixel_to_geo(Dataset.t(), number(), number()) :: {:ok, {float(), float()}} | {:error, error()}
+  def pixel_to_geo(%Dataset{ref: ref}, col, row) do
+    Native.gdal_pixel_to_geo(ref, col / 1, row / 1)
+  end
+
+  @doc """
+  Converts a georeferenced `{x, y}` to a fractional pixel/line `{col, row}`
+  using the inverse geo-transform. Rotated and sheared transforms are handled;
+  returns `{:error, {:invalid_argument, _}}` if the transform is not invertible.
+  """
+  @spec geo_to_pixel(Dataset.t(), number(), number()) :: {:ok, {float(), float()}} | {:error, error()}
+  def geo_to_pixel(%Dataset{ref: ref}, x, y) do
+    Native.gdal_geo_to_pixel(ref, x / 1, y / 1)
+  end
+
   @doc """
   Reads a metadata item. Domain defaults to `""` (the default domain).
   """
-  @spec metadata_item(Dataset.t(), String.t(), String.t()) :: {:ok, String.t() | nil} | {:error, String.t()}
+  @spec metadata_item(Dataset.t(), String.t(), String.t()) :: {:ok, String.t() | nil} | {:error, error()}
   def metadata_item(%Dataset{ref: ref}, key, domain \\ "") do
     Native.gdal_metadata_item(ref, key, domain)
   end
 
   @doc """
-  Lists all metadata domain names present on the dataset.
+  Sets a metadata item, e.g. provenance such as `"PROCESSING_DATE"`. Domain
+  defaults to `""` (the default domain).
 
-  The default domain is represented by `""`.
+  The dataset must be writable: created with `create/6` or opened with
+  `access: :update`, otherwise `{:error, {:invalid_argument, _}}` is
+  returned.
   """
-  @spec metadata_domains(Dataset.t()) :: {:ok, [String.t()]} | {:error, String.t()}
+  @spec set_metadata_item(Dataset.t(), String.t(), String.t(), String.t()) ::
+          :ok | {:error, error()}
+  def set_metadata_item(%Dataset{ref: ref}, key, value, domain \\ "") do
+    Native.gdal_set_metadata_item(ref, key, value, domain)
+  end
+
+  @doc """
+  Lists all metadata domain names present on the dataset, e.g.
+  `["", "IMAGE_STRUCTURE", "SUBDATASETS"]`.
+
+  The default domain is represented by `""`. Pass a name to `metadata/2` to
+  list its items.
+  """
+  @spec metadata_domains(Dataset.t()) :: {:ok, [String.t()]} | {:error, error()}
   def metadata_domains(%Dataset{ref: ref}) do
     Native.gdal_metadata_domains(ref)
   end
@@ -124,26 +1501,101 @@ defmodule ExGdal do
 
   Returns `{:ok, nil}` if the domain does not exist.
   """
-  @spec metadata_domain(Dataset.t(), String.t()) :: {:ok, [String.t()] | nil} | {:error, String.t()}
+  @spec metadata_domain(Dataset.t(), String.t()) :: {:ok, [String.t()] | nil} | {:error, error()}
   def metadata_domain(%Dataset{ref: ref}, domain \\ "") do
     Native.gdal_metadata_domain(ref, domain)
   end
 
+  @doc """
+  Returns all metadata items in a domain as `{key, value}` tuples.
+
+  Domain defaults to `""` (the default domain). A domain that does not exist
+  has no items, so `{:ok, []}` is returned.
+  """
+  @spec metadata(Dataset.t(), String.t()) :: {:ok, [{String.t(), String.t()}]} | {:error, error()}
+  def metadata(%Dataset{ref: ref}, domain \\ "") do
+    Native.gdal_metadata(ref, domain)
+  end
+
+  @doc """
+  Reads a metadata item of a band (1-based index), such as a NetCDF variable
+  attribute. Domain defaults to `""` (the default domain).
+  """
+  @spec band_metadata_item(Dataset.t(), pos_integer(), String.t(), String.t()) ::
+          {:ok, String.t() | nil} | {:error, error()}
+  def band_metadata_item(%Dataset{ref: ref}, band_idx, key, domain \\ "") do
+    Native.gdal_band_metadata_item(ref, band_idx, key, domain)
+  end
+
+  @doc """
+  Returns all metadata items of a band in a domain as `{key, value}` tuples.
+
+  Domain defaults to `""`; a domain that does not exist returns `{:ok, []}`.
+  """
+  @spec band_metadata(Dataset.t(), pos_integer(), String.t()) ::
+          {:ok, [{String.t(), String.t()}]} | {:error, error()}
+  def band_metadata(%Dataset{ref: ref}, band_idx, domain \\ "") do
+    Native.gdal_band_metadata(ref, band_idx, domain)
+  end
+
+  @doc """
+  Lists the subdatasets of a container format such as NetCDF or HDF as
+  `{name, description}` tuples, from the `SUBDATASETS` metadata domain.
+
+  Returns `{:ok, []}` for datasets without subdatasets.
+  """
+  @spec subdatasets(Dataset.t()) :: {:ok, [{String.t(), String.t()}]} | {:error, error()}
+  def subdatasets(%Dataset{ref: ref}) do
+    Native.gdal_subdatasets(ref)
+  end
+
+  @doc """
+  Opens a subdataset by the connection string `name` returned from
+  `subdatasets/1`, e.g. `"NETCDF:\"climate.nc\":tas"`.
+
+  Returns `{:error, {:invalid_argument, _}}` if `name` is not one of the
+  dataset's subdatasets.
+  """
+  @spec open_subdataset(Dataset.t(), String.t()) :: {:ok, Dataset.t()} | {:error, error()}
+  def open_subdataset(%Dataset{ref: ref}, name) do
+    with {:ok, sub} <- Native.gdal_open_subdataset(ref, name) do
+      wrap(sub, name)
+    end
+  end
+
+  @doc """
+  Returns the dataset's description, the name GDAL assigned when opening:
+  the path, the `/vsimem/` file of `open_memory/2`, or the connection
+  string of a subdataset.
+  """
+  @spec description(Dataset.t()) :: {:ok, String.t()} | {:error, error()}
+  def description(%Dataset{ref: ref}) do
+    Native.gdal_description(ref)
+  end
+
   @doc """
   Returns the description string for a band (1-based index).
 
   For concentration rasters produced by PlumeFutures, this is
   the ISO 8601 timestamp of the time step.
   """
-  @spec band_description(Dataset.t(), pos_integer()) :: {:ok, String.t()} | {:error, String.t()}
+  @spec band_description(Dataset.t(), pos_integer()) :: {:ok, String.t()} | {:error, error()}
   def band_description(%Dataset{ref: ref}, band_idx) do
     Native.gdal_band_description(ref, band_idx)
   end
 
+  @doc """
+  Sets the description string for a band (1-based index), e.g. `"NDVI"`.
+  """
+  @spec set_band_description(Dataset.t(), pos_integer(), String.t()) :: :ok | {:error, error()}
+  def set_band_description(%Dataset{ref: ref}, band_idx, description) do
+    Native.gdal_set_band_description(ref, band_idx, description)
+  end
+
   @doc """
   Returns all band descriptions as a list of strings.
   """
-  @spec band_descriptions(Dataset.t()) :: {:ok, [String.t()]} | {:error, String.t()}
+  @spec band_descriptions(Dataset.t()) :: {:ok, [String.t()]} | {:error, error()}
   def band_descriptions(%Dataset{raster_count: count, ref: ref}) do
     results =
       Enum.reduce_while(1..count, [], fn i, acc ->
@@ -160,6 +1612,265 @@ defmodule ExGdal do
   end
 
   @doc "Returns the short driver name (e.g. `\"GTiff\"`)."
-  @spec driver_name(Dataset.t()) :: {:ok, String.t()} | {:error, String.t()}
+  @spec driver_name(Dataset.t()) :: {:ok, String.t()} | {:error, error()}
   def driver_name(%Dataset{driver: driver}), do: {:ok, driver}
+
+  @doc """
+  Returns a `gdalinfo`-style summary of the dataset in one call:
+  `raster_size`, `band_count`, `driver`, `geo_transform` (a `%GeoTransform{}`),
+  `spatial_ref_wkt` and `bands`, a list with each band's `type` and
+  `no_data_value`. A missing geo-transform, SRS or nodata value is `nil`.
+  """
+  @spec info(Dataset.t()) ::
+          {:ok,
+           %{
+             raster_size: {non_neg_integer(), non_neg_integer()},
+             band_count: non_neg_integer(),
+             driver: String.t(),
+             geo_transform: GeoTransform.t() | nil,
+             spatial_ref_wkt: String.t() | nil,
+             bands: [%{type: atom(), no_data_value: float() | nil}]
+           }}
+          | {:error, error()}
+  def info(%Dataset{ref: ref}) do
+    with {:ok, info} <- Native.gdal_info(ref) do
+      geo_transform = info.geo_transform && GeoTransform.from_list(info.geo_transform)
+      {:ok, %{info | geo_transform: geo_transform}}
+    end
+  end
+
+  @doc """
+  Returns every file the dataset is made of: the primary file plus sidecars
+  such as `.aux.xml`, `.ovr`, world files or a shapefile's `.dbf` and
+  `.prj`, e.g. to move or delete a dataset as a whole. In-memory datasets
+  return `[]`.
+  """
+  @spec file_list(Dataset.t()) :: {:ok, [String.t()]} | {:error, error()}
+  def file_list(%Dataset{ref: ref}) do
+    Native.gdal_file_list(ref)
+  end
+
+  @doc """
+  Lists the drivers registered in the linked GDAL, with whether each can
+  create datasets directly (`create/6`) or only as copies (`copy/4`).
+  """
+  @spec driver_list() ::
+          {:ok,
+           [
+             %{
+               short_name: String.t(),
+               long_name: String.t(),
+               can_create: boolean(),
+               can_create_copy: boolean()
+             }
+           ]}
+  def driver_list, do: {:ok, Native.gdal_driver_list()}
+
+  @doc """
+  Returns the file extensions (without the dot, most common first) and
+  MIME type of the files a driver writes, e.g.
+  `%{extensions: ["tif", "tiff"], mime_type: "image/tiff"}` for `"GTiff"`.
+  `mime_type` is `nil` when the driver does not declare one.
+
+  Returns `{:error, {:invalid_argument, _}}` for an unknown driver.
+  """
+  @spec driver_metadata(String.t()) ::
+          {:ok, %{extensions: [String.t()], mime_type: String.t() | nil}} | {:error, error()}
+  def driver_metadata(driver) do
+    Native.gdal_driver_metadata(driver)
+  end
+
+  @doc """
+  Deletes the dataset at `path` through `driver` (e.g. `"GTiff"`), removing
+  its sidecar files (`.aux.xml`, `.ovr`, a shapefile's `.dbf`, ...) along
+  with the primary file, unlike `File.rm/1`. Close the dataset first.
+  """
+  @spec delete(String.t(), Path.t()) :: :ok | {:error, error()}
+  def delete(driver, path) do
+    Native.gdal_delete(driver, Path.expand(path))
+  end
+
+  @doc """
+  Renames the dataset at `old_path` to `new_path` through `driver`, moving
+  its sidecar files with it so that, for example, georeferencing kept in
+  an `.aux.xml` stays attached. Close the dataset first.
+
+  Arguments follow GDAL's `GDALRenameDataset` order: new path, then old.
+  """
+  @spec rename(String.t(), Path.t(), Path.t()) :: :ok | {:error, error()}
+  def rename(driver, new_path, old_path) do
+    Native.gdal_rename(driver, Path.expand(new_path), Path.expand(old_path))
+  end
+
+  @doc """
+  Returns the number of vector layers, e.g. in a GeoPackage or Shapefile.
+
+  Vector datasets are opened with `open/1` like rasters; raster-only formats
+  have no layers.
+  """
+  @spec layer_count(Dataset.t()) :: {:ok, non_neg_integer()} | {:error, error()}
+  def layer_count(%Dataset{ref: ref}) do
+    Native.gdal_layer_count(ref)
+  end
+
+  @doc "Returns the vector layer names, in layer index order (0-based)."
+  @spec layer_names(Dataset.t()) :: {:ok, [String.t()]} | {:error, error()}
+  def layer_names(%Dataset{ref: ref}) do
+    Native.gdal_layer_names(ref)
+  end
+
+  @doc """
+  Returns the number of features in a vector layer (0-based index).
+
+  Some drivers scan the whole layer to count its features.
+  """
+  @spec layer_feature_count(Dataset.t(), non_neg_integer()) ::
+          {:ok, non_neg_integer()} | {:error, error()}
+  def layer_feature_count(%Dataset{ref: ref}, layer_idx) do
+    Native.gdal_layer_feature_count(ref, layer_idx)
+  end
+
+  @doc """
+  Reads up to `max_count` features from a vector layer (0-based index) as
+  `%{fid: _, geometry_wkt: _, fields: %{name => value}}` maps.
+
+  Field values are integers, floats, strings or lists of those; dates and
+  date-times are ISO 8601 strings, and unset fields are `nil`. Features
+  without a geometry have `geometry_wkt: nil`. Returns
+  `{:error, {:invalid_layer, _}}` for a layer index out of range.
+  """
+  @spec read_features(Dataset.t(), non_neg_integer(), non_neg_integer()) ::
+          {:ok,
+           [
+             %{
+               fid: non_neg_integer() | nil,
+               geometry_wkt: String.t() | nil,
+               fields: %{String.t() => term()}
+             }
+           ]}
+          | {:error, error()}
+  def read_features(%Dataset{ref: ref}, layer_idx, max_count) do
+    Native.gdal_read_features(ref, layer_idx, max_count)
+  end
+
+  @doc """
+  Returns the geometry type of a vector layer (0-based index): `:point`,
+  `:line_string`, `:polygon`, `:multi_point`, `:multi_line_string`,
+  `:multi_polygon`, `:geometry_collection`, `:none` for layers without
+  geometry, or `:unknown` for mixed and other types. 3D and measured
+  variants report their 2D type.
+  """
+  @spec layer_geometry_type(Dataset.t(), non_neg_integer()) :: {:ok, atom()} | {:error, error()}
+  def layer_geometry_type(%Dataset{ref: ref}, layer_idx) do
+    Native.gdal_layer_geometry_type(ref, layer_idx)
+  end
+
+  @doc """
+  Returns the bounding box of a vector layer (0-based index) as
+  `{min_x, min_y, max_x, max_y}`, scanning its features if the driver does
+  not store it. Returns an error for a layer without any geometry.
+  """
+  @spec layer_extent(Dataset.t(), non_neg_integer()) ::
+          {:ok, {float(), float(), float(), float()}} | {:error, error()}
+  def layer_extent(%Dataset{ref: ref}, layer_idx) do
+    Native.gdal_layer_extent(ref, layer_idx)
+  end
+
+  @doc """
+  Burns the geometries of a vector layer (0-based index) of `source` into a
+  band of the raster `target`, using the target's geo-transform. Geometries
+  must already be in the target's coordinate system. `source` and `target`
+  may be the same dataset, such as a GeoPackage with both a layer and a
+  raster.
+
+  Options:
+
+    * `:band` - target band index, default `1`
+    * `:burn_value` - value burned for every geometry, default `1`
+    * `:attribute` - name of a numeric field to burn instead of
+      `:burn_value`; features where it is null are skipped
+  """
+  @spec rasterize_layer(Dataset.t(), Dataset.t(), non_neg_integer(), keyword()) ::
+          :ok | {:error, error()}
+  def rasterize_layer(%Dataset{ref: target}, %Dataset{ref: source}, layer_idx, opts \\ []) do
+    band_idx = Keyword.get(opts, :band, 1)
+    burn_value = Keyword.get(opts, :burn_value, 1)
+    attribute = Keyword.get(opts, :attribute)
+
+    Native.gdal_rasterize_layer(target, band_idx, source, layer_idx, burn_value / 1, attribute)
+  end
+
+  @doc """
+  Vectorizes a band of `source` into a new polygon layer of the vector
+  dataset `target`, one feature per connected region of equal pixel
+  value. Returns the new layer's 0-based index.
+
+  The layer takes the source's spatial reference and stores each region's
+  pixel value in a single field (an integer, or a real for float bands).
+  Pixels excluded by the band's mask, such as nodata, produce no polygons.
+
+  Options:
+
+    * `:layer` - name of the layer to create, default `"polygons"`
+    * `:field` - name of the pixel value field, default `"value"`
+  """
+  @spec polygonize(Dataset.t(), pos_integer(), Dataset.t(), keyword()) ::
+          {:ok, non_neg_integer()} | {:error, error()}
+  def polygonize(%Dataset{ref: source}, band_idx, %Dataset{ref: target}, opts \\ []) do
+    layer = Keyword.get(opts, :layer, "polygons")
+    field = Keyword.get(opts, :field, "value")
+    Native.gdal_polygonize(source, band_idx, target, layer, field)
+  end
+
+  @doc """
+  Fills nodata gaps of a band in place by interpolating from valid pixels
+  within `max_search_distance` pixels, then applying `smoothing_iterations`
+  3x3 smoothing passes over the filled area (`0` for none).
+
+  Pixels excluded by the band's mask, such as nodata, are filled. The
+  dataset must be writable: one from `create/6` or opened with
+  `open_ex(path, access: :update)`.
+  """
+  @spec fill_nodata(Dataset.t(), pos_integer(), number(), non_neg_integer()) ::
+          :ok | {:error, error()}
+  def fill_nodata(%Dataset{ref: ref}, band_idx, max_search_distance, smoothing_iterations \\ 0) do
+    Native.gdal_fill_nodata(ref, band_idx, max_search_distance / 1, smoothing_iterations)
+  end
+
+  @doc """
+  Removes speckle from a classified band in place: each connected region
+  smaller than `size_threshold` pixels is merged into its largest
+  neighbouring region. `connectedness` is `4` (edges only) or `8` (edges
+  and corners).
+
+  Pixels excluded by the band's mask are left untouched. The dataset must
+  be writable, as for `fill_nodata/4`.
+  """
+  @spec sieve_filter(Dataset.t(), pos_integer(), pos_integer(), 4 | 8) :: :ok | {:error, error()}
+  def sieve_filter(%Dataset{ref: ref}, band_idx, size_threshold, connectedness \\ 4) do
+    Native.gdal_sieve_filter(ref, band_idx, size_threshold, connectedness)
+  end
+
+  @doc """
+  Traces contour lines of a band into a new line layer of the vector
+  dataset `target` and returns the layer's 0-based index. `levels` is
+  either an interval (a line every `levels` units, starting from 0) or an
+  explicit list of levels.
+
+  The layer takes the source's spatial reference and stores each line's
+  level in a real field. The band's nodata value is excluded.
+
+  Options:
+
+    * `:layer` - name of the layer to create, default `"contours"`
+    * `:field` - name of the level field, default `"elev"`
+  """
+  @spec contour(Dataset.t(), pos_integer(), number() | [number()], Dataset.t(), keyword()) ::
+          {:ok, non_neg_integer()} | {:error, error()}
+  def contour(%Dataset{ref: source}, band_idx, levels, %Dataset{ref: target}, opts \\ []) do
+    levels = if is_list(levels), do: Enum.map(levels, &(&1 / 1)), else: levels / 1
+    layer = Keyword.get(opts, :layer, "contours")
+    field = Keyword.get(opts, :field, "elev")
+    Native.gdal_contour(source, band_idx, levels, target, layer, field)
+  end
 end
@@ -0,0 +1,18 @@
+defmodule ExGdal.Band do
+  @moduledoc """
+  Wraps a NIF reference to one raster band, created with `ExGdal.band/2`.
+
+  The band is looked up once, so hot loops reading the same band through
+  `ExGdal.read_band/1` and `ExGdal.read_band_window/2` skip the per-call
+  lookup. It keeps its dataset alive; once the dataset is closed with
+  `ExGdal.close/1`, reads return `{:error, {:closed, _}}`.
+  """
+
+  defstruct [:ref, :dataset, :index]
+
+  @type t :: %__MODULE__{
+          ref: reference(),
+          dataset: ExGdal.Dataset.t(),
+          index: pos_integer()
+        }
+end
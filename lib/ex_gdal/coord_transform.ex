@@ -0,0 +1,18 @@
+defmodule ExGdal.CoordTransform do
+  @moduledoc """
+  Wraps a NIF reference to a coordinate transformation between two spatial
+  references, created with `ExGdal.create_coord_transform/2`.
+
+  The transform is built once and reused by `ExGdal.transform_with/2`, so
+  repeated batches skip the PROJ pipeline setup `ExGdal.transform_coords/3`
+  does on every call. It is safe to share between processes.
+  """
+
+  defstruct [:ref, :source, :target]
+
+  @type t :: %__MODULE__{
+          ref: reference(),
+          source: pos_integer() | String.t(),
+          target: pos_integer() | String.t()
+        }
+end
@@ -1,13 +1,15 @@
 defmodule ExGdal.Dataset do
   @moduledoc """
   Wraps a NIF reference to an opened GDAL dataset.
+
+  `path` is `nil` for datasets opened from memory with `ExGdal.open_memory/2`.
   """
 
   defstruct [:ref, :path, :raster_count, :raster_size, :driver]
 
   @type t :: %__MODULE__{
           ref: reference(),
-          path: String.t(),
+          path: String.t() | nil,
           raster_count: non_neg_integer(),
           raster_size: {non_neg_integer(), non_neg_integer()},
           driver: String.t()
@@ -30,4 +30,9 @@ defmodule ExGdal.GeoTransform do
       pixel_height: pixel_height
     }
   end
+
+  @doc "Convert back to GDAL's [f64; 6] coefficient order."
+  def to_list(%__MODULE__{} = gt) do
+    [gt.origin_x, gt.pixel_width, gt.skew_x, gt.origin_y, gt.skew_y, gt.pixel_height]
+  end
 end
@@ -4,18 +4,182 @@ defmodule ExGdal.Native do
   use Rustler, otp_app: :ex_gdal, crate: "ex_gdal_nif"
 
   def gdal_open(_path), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_open_ex(_path, _access, _open_options, _allowed_drivers),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_open_with_warnings(_path, _access, _open_options, _allowed_drivers),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_open_memory(_bytes, _file_name), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_open_vsicurl(_url, _options), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_open_timeout(_path, _timeout_ms), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_open_zip(_archive, _inner_path), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_list_zip(_archive), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_open_threadsafe(_path), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_create(_driver, _path, _width, _height, _band_count, _data_type), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_config_option(_key, _value), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_get_config_option(_key), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_cache_max(_bytes), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_get_cache_used, do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_create_vector(_driver, _path), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_copy(_resource, _driver, _path, _creation_options),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_create_copy_progress(_resource, _driver, _path, _creation_options, _pid),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_to_mem(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_close(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_raster_count(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_raster_size(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_dimensions(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_size(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_read_band(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_resampled(_resource, _band_idx, _width, _height, _resampling),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_read_band_typed(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_rows(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_list(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_pixel(_resource, _band_idx, _col, _row), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_sample_points(_resource, _band_idx, _points), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_sample_coords(_resource, _band_idx, _coords, _interpolation),
+    do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_mask_band(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_mask_flags(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_bands(_resource, _band_indices), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_rgba(_resource, _window), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_le(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_as_float(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_band_masked(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_native_endianness(), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_version(), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_read_band_window(_resource, _band_idx, _x, _y, _w, _h), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_windows(_resource, _band_idx, _windows), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_read(_band), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_read_window(_band, _window), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_read_band_window_resampled(_band, _x, _y, _w, _h, _out_w, _out_h, _resampling),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_raster_io(_band, _x, _y, _w, _h, _out_w, _out_h, _pixel_space, _line_space),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_block_size(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_block(_resource, _band_idx, _block_x, _block_y), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_stream_blocks(_resource, _band_idx, _pid), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_overview_count(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_overview_sizes(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_build_overviews(_resource, _resampling, _factors), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_read_overview(_resource, _band_idx, _overview_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_read_for_resolution(_resource, _band_idx, _window, _out_size, _resampling),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_write_band(_resource, _band_idx, _data, _data_type), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_flush_cache(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_band_type(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_type_info(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_nx_type(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_data_type_size(_data_type), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_color_interp(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_band_color_interp(_resource, _band_idx, _interp), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_is_paletted(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_has_alpha(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_color_table(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_no_data_value(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_set_no_data_value(_resource, _band_idx, _value),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_delete_no_data_value(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_checksum(_resource, _band_idx, _window), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_statistics(_resource, _band_idx, _approx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_min_max(_resource, _band_idx, _approx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_histogram(_resource, _band_idx, _min, _max, _buckets, _include_out_of_range),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_band_scale(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_offset(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_unit_type(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_category_names(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_rat(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_spatial_ref_wkt(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_spatial_ref_proj4(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_spatial_ref_epsg(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_gcp_count(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_gcps(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_gcp_spatial_ref_wkt(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_transform_coords(_source, _target, _points),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_create_coord_transform(_source, _target), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_transform_with(_transform, _points), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_spatial_ref(_resource, _definition), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_geo_transform(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_geo_transform_map(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_geo_transform(_resource, _coefficients), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_pixel_to_geo(_resource, _col, _row), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_geo_to_pixel(_resource, _x, _y), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_metadata_item(_resource, _key, _domain), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_metadata_item(_resource, _key, _value, _domain),
+    do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_metadata(_resource, _domain), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_metadata_domains(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_metadata_domain(_resource, _domain), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_band_metadata_item(_resource, _band_idx, _key, _domain),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_band_metadata(_resource, _band_idx, _domain), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_subdatasets(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_open_subdataset(_resource, _name), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_description(_resource), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_band_description(_resource, _band_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_set_band_description(_resource, _band_idx, _description), do: :erlang.nif_error(:nif_not_loaded)
   def gdal_driver_name(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_info(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_file_list(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_driver_list(), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_driver_metadata(_driver), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_delete(_driver, _path), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_rename(_driver, _new_path, _old_path), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_layer_count(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_layer_names(_resource), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_layer_feature_count(_resource, _layer_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_read_features(_resource, _layer_idx, _max_count),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_layer_geometry_type(_resource, _layer_idx), do: :erlang.nif_error(:nif_not_loaded)
+  def gdal_layer_extent(_resource, _layer_idx), do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_rasterize_layer(_target, _band_idx, _source, _layer_idx, _burn_value, _attribute),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_polygonize(_source, _band_idx, _target, _layer_name, _field_name),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_fill_nodata(_resource, _band_idx, _max_search_distance, _smoothing_iterations),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_sieve_filter(_resource, _band_idx, _size_threshold, _connectedness),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_contour(_source, _band_idx, _levels, _target, _layer_name, _field_name),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_warp(_source, _srs, _resolution, _resampling, _output),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_translate(_source, _bands, _data_type, _scale, _window, _output),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_encode_window(_source, _window, _out_size, _format, _quality),
+    do: :erlang.nif_error(:nif_not_loaded)
+
+  def gdal_dem_processing(_source, _band_idx, _mode, _output, _z_factor, _scale, _light),
+    do: :erlang.nif_error(:nif_not_loaded)
 end
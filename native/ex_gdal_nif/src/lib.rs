@@ -1,13 +1,49 @@
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
 
-use gdal::raster::GdalDataType;
-use gdal::{Dataset, Metadata};
-use rustler::{Atom, Binary, Env, NewBinary, ResourceArc};
+use gdal::cpl::CslStringList;
+use gdal::errors::GdalError;
+use gdal::raster::{Buffer, ColorInterpretation, GdalDataType, GdalType, RasterBand, ResampleAlg};
+use gdal::spatial_ref::{AxisMappingStrategy, CoordTransform, SpatialRef};
+use gdal::vector::{
+    geometry_type_flatten, FieldValue, LayerAccess, LayerOptions, OGRFieldType, OGRwkbGeometryType,
+};
+use gdal::version::VersionInfo;
+use gdal::{
+    Dataset, DatasetOptions, Driver, DriverManager, GdalOpenFlags, GeoTransformEx, Metadata,
+};
+use rustler::{
+    Atom, Binary, Encoder, Env, LocalPid, NewBinary, NifMap, NifResult, NifUntaggedEnum, OwnedEnv,
+    ResourceArc, Term,
+};
 
 mod atoms {
     rustler::atoms! {
         ok,
         error,
+        // Error kinds, the first element of `{:error, {kind, message}}`
+        open_failed,
+        invalid_band,
+        invalid_argument,
+        io_error,
+        closed,
+        gdal_error,
+        all_nodata,
+        no_spatial_ref,
+        transform_failed,
+        invalid_layer,
+        timeout,
+        // Messages sent by gdal_stream_blocks and gdal_create_copy_progress
+        block,
+        done,
+        progress,
+        // Access modes for gdal_open_ex
+        read_only,
+        update,
         unknown,
         uint8,
         int8,
@@ -19,273 +55,4384 @@ mod atoms {
         int64,
         float32,
         float64,
+        // Byte orders reported by gdal_native_endianness. Band readers emit
+        // native-endian bytes unless their name says otherwise (e.g. _le).
+        little,
+        big,
+        // Nx tensor types of the bytes read_window_bytes produces
+        u8,
+        s16,
+        u16,
+        s32,
+        u32,
+        f32,
+        f64,
+        nearest,
+        bilinear,
+        average,
+        gauss,
+        cubic,
+        lanczos,
+        undefined,
+        gray,
+        palette,
+        red,
+        green,
+        blue,
+        alpha,
+        hue,
+        saturation,
+        lightness,
+        cyan,
+        magenta,
+        yellow,
+        black,
+        ycbcr_y,
+        ycbcr_cb,
+        ycbcr_cr,
+        // Raster attribute table column types and usages
+        integer,
+        real,
+        string,
+        generic,
+        pixel_count,
+        name,
+        min,
+        max,
+        min_max,
+        red_min,
+        green_min,
+        blue_min,
+        alpha_min,
+        red_max,
+        green_max,
+        blue_max,
+        alpha_max,
+        // Vector layer geometry types
+        none,
+        point,
+        line_string,
+        polygon,
+        multi_point,
+        multi_line_string,
+        multi_polygon,
+        geometry_collection,
+        // DEM processing modes
+        hillshade,
+        slope,
+        aspect,
+        roughness,
+        tri,
+        tpi,
+        // Encoded image formats
+        png,
+        jpeg,
     }
 }
 
 struct DatasetResource {
-    inner: Mutex<Dataset>,
+    /// `None` once the dataset has been released with `gdal_close`.
+    inner: Mutex<Option<Dataset>>,
+    /// Further read-only handles to the same file, opened by
+    /// `gdal_open_threadsafe` so concurrent reads need not share one lock.
+    readers: Vec<Mutex<Option<Dataset>>>,
+    /// Rotates the handle `lock` tries first, spreading readers out.
+    next_reader: AtomicUsize,
+    /// In-memory file backing the dataset, unlinked once the dataset closes.
+    vsimem_path: Option<String>,
 }
 
 #[rustler::resource_impl]
 impl rustler::Resource for DatasetResource {}
 
-fn gdal_err_to_string(e: gdal::errors::GdalError) -> String {
-    format!("{e}")
+impl DatasetResource {
+    fn new(ds: Dataset) -> Self {
+        DatasetResource {
+            inner: Mutex::new(Some(ds)),
+            readers: Vec::new(),
+            next_reader: AtomicUsize::new(0),
+            vsimem_path: None,
+        }
+    }
+
+    /// A dataset opened from the in-memory file at `path`, which is freed
+    /// along with it.
+    fn with_vsimem(ds: Dataset, path: String) -> Self {
+        let mut resource = Self::new(ds);
+        resource.vsimem_path = Some(path);
+        resource
+    }
+
+    /// A dataset with extra read-only handles on the same file, which
+    /// `lock` spreads reads across.
+    fn with_readers(ds: Dataset, readers: Vec<Dataset>) -> Self {
+        let mut resource = Self::new(ds);
+        resource.readers = readers.into_iter().map(|r| Mutex::new(Some(r))).collect();
+        resource
+    }
+
+    /// Drops the dataset, then frees its in-memory file, if any. The file
+    /// must outlive the dataset, which may still read from it while closing.
+    fn release(&self, inner: &mut Option<Dataset>) {
+        for reader in &self.readers {
+            lock_recovering(reader).take();
+        }
+        if inner.take().is_some() {
+            if let Some(path) = &self.vsimem_path {
+                let _ = gdal::vsi::unlink_mem_file(path);
+            }
+        }
+    }
+
+    /// Locks the dataset, failing if it has already been closed. With
+    /// reader handles, this is whichever handle is free, waiting on one
+    /// only when all are busy.
+    fn lock(&self) -> Result<DatasetGuard<'_>, Error> {
+        if self.readers.is_empty() {
+            return self.lock_primary();
+        }
+
+        let handles: Vec<&Mutex<Option<Dataset>>> =
+            std::iter::once(&self.inner).chain(&self.readers).collect();
+        let start = self.next_reader.fetch_add(1, Ordering::Relaxed) % handles.len();
+        let rotated = handles[start..].iter().chain(&handles[..start]);
+        let guard = rotated
+            .filter_map(|handle| match handle.try_lock() {
+                Ok(guard) => Some(guard),
+                Err(TryLockError::Poisoned(poisoned)) => {
+                    handle.clear_poison();
+                    Some(poisoned.into_inner())
+                }
+                Err(TryLockError::WouldBlock) => None,
+            })
+            .next()
+            .unwrap_or_else(|| lock_recovering(handles[start]));
+        open_guard(guard)
+    }
+
+    /// Locks the handle the dataset was first opened with, for callers that
+    /// keep raw handles to its bands.
+    fn lock_primary(&self) -> Result<DatasetGuard<'_>, Error> {
+        open_guard(lock_recovering(&self.inner))
+    }
+
+    /// Locks the dataset for a change to its pixels or metadata, failing
+    /// unless it was opened for update. Reader handles are always
+    /// read-only, so a change can never reach just one of them.
+    fn lock_for_update(&self) -> Result<DatasetGuard<'_>, Error> {
+        let ds = self.lock_primary()?;
+        ensure_writable(&ds)?;
+        Ok(ds)
+    }
+}
+
+fn open_guard(guard: MutexGuard<'_, Option<Dataset>>) -> Result<DatasetGuard<'_>, Error> {
+    if guard.is_none() {
+        return Err(Error::new(atoms::closed(), "dataset closed"));
+    }
+    Ok(DatasetGuard(guard))
+}
+
+/// Locks `source` for reading and `target` for update, for algorithms that
+/// read one dataset while writing a layer into another. The pair is always
+/// locked in address order, so two calls with source and target swapped
+/// cannot each hold one lock and wait for the other.
+fn lock_source_target<'a>(
+    source: &'a DatasetResource,
+    target: &'a DatasetResource,
+) -> Result<(DatasetGuard<'a>, DatasetGuard<'a>), Error> {
+    // Both stay locked while the algorithm runs, so the same resource
+    // twice would deadlock on its own lock.
+    if std::ptr::eq(target, source) {
+        return Err(Error::invalid_argument(
+            "source and target must be different datasets",
+        ));
+    }
+    if std::ptr::from_ref(source) < std::ptr::from_ref(target) {
+        let src = source.lock()?;
+        Ok((src, target.lock_for_update()?))
+    } else {
+        let ds = target.lock_for_update()?;
+        Ok((source.lock()?, ds))
+    }
+}
+
+impl Drop for DatasetResource {
+    fn drop(&mut self) {
+        let inner = self.inner.get_mut().unwrap_or_else(|e| e.into_inner());
+        let mut ds = inner.take();
+        self.release(&mut ds);
+    }
+}
+
+/// A locked, still-open dataset.
+struct DatasetGuard<'a>(MutexGuard<'a, Option<Dataset>>);
+
+impl Deref for DatasetGuard<'_> {
+    type Target = Dataset;
+
+    fn deref(&self) -> &Dataset {
+        self.0.as_ref().expect("checked in DatasetResource::lock")
+    }
+}
+
+impl DerefMut for DatasetGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Dataset {
+        self.0.as_mut().expect("checked in DatasetResource::lock")
+    }
+}
+
+/// A coordinate transform kept alive across calls, so the PROJ pipeline is
+/// set up once rather than per batch of points.
+struct CoordTransformResource {
+    inner: Mutex<SendCoordTransform>,
+}
+
+#[rustler::resource_impl]
+impl rustler::Resource for CoordTransformResource {}
+
+struct SendCoordTransform(CoordTransform);
+
+// SAFETY: an OGRCoordinateTransformation has no thread affinity; it only
+// must not be used by two threads at once, which the mutex rules out.
+unsafe impl Send for SendCoordTransform {}
+
+impl CoordTransformResource {
+    fn lock(&self) -> MutexGuard<'_, SendCoordTransform> {
+        lock_recovering(&self.inner)
+    }
+}
+
+/// A band handle looked up once by `gdal_band`, for repeated reads of the
+/// same band. It keeps its dataset resource alive and only uses the handle
+/// under that dataset's lock, after checking it is still open.
+struct BandResource {
+    dataset: ResourceArc<DatasetResource>,
+    c_band: SendBandHandle,
+}
+
+#[rustler::resource_impl]
+impl rustler::Resource for BandResource {}
+
+struct SendBandHandle(gdal_sys::GDALRasterBandH);
+
+// SAFETY: the handle is only dereferenced while the owning dataset's mutex
+// is held, so it is never used by two threads at once.
+unsafe impl Send for SendBandHandle {}
+unsafe impl Sync for SendBandHandle {}
+
+impl BandResource {
+    /// Locks the dataset and passes `f` the band, failing if the dataset
+    /// has been closed; a band's handle is freed along with its dataset.
+    fn with_band<T>(&self, f: impl FnOnce(&RasterBand) -> Result<T, Error>) -> Result<T, Error> {
+        let ds = self.dataset.lock_primary()?;
+        // SAFETY: GDAL owns the band for as long as the dataset is open,
+        // which the guard guarantees for the duration of `f`.
+        let band = unsafe { RasterBand::from_c_rasterband(&ds, self.c_band.0) };
+        f(&band)
+    }
+}
+
+/// Locks `mutex` even if a NIF panicked while holding it. The guarded
+/// values are GDAL handles with no Rust-side invariants a panic could leave
+/// half-updated, so they stay usable; the poison is cleared rather than
+/// failing every later call on the resource.
+fn lock_recovering<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| {
+        mutex.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
+/// Error returned to Elixir as `{kind, message}`, where `kind` is an atom
+/// callers can match on and `message` is human-readable detail.
+struct Error {
+    kind: Atom,
+    message: String,
+    /// `(index, band_count)` of a bad band index, which is returned as
+    /// `{:invalid_band, index, band_count}` so callers need not parse it.
+    band_range: Option<(usize, usize)>,
+}
+
+impl Error {
+    fn new(kind: Atom, message: impl Into<String>) -> Self {
+        Error {
+            kind,
+            message: message.into(),
+            band_range: None,
+        }
+    }
+
+    fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(atoms::invalid_argument(), message)
+    }
+
+    fn invalid_band(band_idx: usize, band_count: usize) -> Self {
+        Error {
+            band_range: Some((band_idx, band_count)),
+            ..Self::new(
+                atoms::invalid_band(),
+                format!(
+                    "band index {band_idx} out of range, dataset has {band_count} bands \
+                     (indices are 1-based)"
+                ),
+            )
+        }
+    }
+}
+
+impl Encoder for Error {
+    fn encode<'a>(&self, env: Env<'a>) -> Term<'a> {
+        match self.band_range {
+            Some((band_idx, band_count)) => (self.kind, band_idx, band_count).encode(env),
+            None => (self.kind, &self.message).encode(env),
+        }
+    }
+}
+
+/// Lets `NifResult` NIFs return `{:error, {kind, message}}` (or the band
+/// form) via `?`.
+impl From<Error> for rustler::Error {
+    fn from(e: Error) -> Self {
+        rustler::Error::Term(Box::new(e))
+    }
+}
+
+/// Fetches a 1-based band, checking the index first: GDAL's own error for a
+/// bad index varies between versions, and index 0 is a common mistake.
+fn raster_band(ds: &Dataset, band_idx: usize) -> Result<RasterBand<'_>, Error> {
+    check_band_index(band_idx, ds.raster_count())?;
+    Ok(ds.rasterband(band_idx)?)
+}
+
+fn check_band_index(band_idx: usize, band_count: usize) -> Result<(), Error> {
+    if band_idx == 0 || band_idx > band_count {
+        return Err(Error::invalid_band(band_idx, band_count));
+    }
+    Ok(())
+}
+
+// CPLErrorNum values (#defines in cpl_error.h, so not in the bindings)
+const CPLE_FILE_IO: c_int = 3;
+const CPLE_OPEN_FAILED: c_int = 4;
+const CPLE_HTTP_RESPONSE: c_int = 11;
+
+impl From<GdalError> for Error {
+    fn from(e: GdalError) -> Self {
+        let kind = match &e {
+            GdalError::NullPointer { method_name, .. } if method_name.starts_with("GDALOpen") => {
+                atoms::open_failed()
+            }
+            GdalError::NullPointer {
+                method_name: "GDALGetRasterBand",
+                ..
+            } => atoms::invalid_band(),
+            GdalError::NullPointer {
+                method_name: "GDALDatasetGetLayer",
+                ..
+            } => atoms::invalid_layer(),
+            GdalError::NullPointer {
+                method_name: "GDALGetSpatialRef",
+                ..
+            } => atoms::no_spatial_ref(),
+            GdalError::NullPointer {
+                method_name: "OCTNewCoordinateTransformation",
+                ..
+            }
+            | GdalError::InvalidCoordinateRange { .. } => atoms::transform_failed(),
+            GdalError::OgrError {
+                method_name: "OSRSetFromUserInput" | "OSRImportFromEPSG",
+                ..
+            } => atoms::invalid_argument(),
+            GdalError::CplError { number, .. } => match *number {
+                CPLE_OPEN_FAILED => atoms::open_failed(),
+                CPLE_FILE_IO | CPLE_HTTP_RESPONSE => atoms::io_error(),
+                _ => atoms::gdal_error(),
+            },
+            GdalError::BadArgument(_)
+            | GdalError::FfiNulError(_)
+            | GdalError::IntConversionError(_)
+            | GdalError::InvalidFieldName { .. }
+            | GdalError::InvalidFieldIndex { .. } => atoms::invalid_argument(),
+            _ => atoms::gdal_error(),
+        };
+        // Prefer GDAL's own message over the wrapper's description of the call
+        let message = match &e {
+            GdalError::CplError { msg, .. } | GdalError::NullPointer { msg, .. }
+                if !msg.is_empty() =>
+            {
+                msg.clone()
+            }
+            _ => e.to_string(),
+        };
+        Error::new(kind, message)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open / open_ex — open_ex takes open options and allowed drivers
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open(path: String) -> Result<ResourceArc<DatasetResource>, Error> {
+    open_ex(&path, GdalOpenFlags::GDAL_OF_READONLY, &[], None)
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_ex(
+    path: String,
+    access: Atom,
+    open_options: Vec<String>,
+    allowed_drivers: Option<Vec<String>>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let open_flags = open_flags(access)?;
+    open_ex(&path, open_flags, &open_options, allowed_drivers.as_deref())
+}
+
+fn open_flags(access: Atom) -> Result<GdalOpenFlags, Error> {
+    match access {
+        a if a == atoms::read_only() => Ok(GdalOpenFlags::GDAL_OF_READONLY),
+        a if a == atoms::update() => Ok(GdalOpenFlags::GDAL_OF_UPDATE),
+        _ => Err(Error::invalid_argument(
+            "access must be :read_only or :update",
+        )),
+    }
+}
+
+/// `open_options` are `KEY=VALUE` strings; `allowed_drivers` restricts which
+/// drivers GDAL tries, `None` allowing all of them.
+fn open_ex(
+    path: &str,
+    open_flags: GdalOpenFlags,
+    open_options: &[String],
+    allowed_drivers: Option<&[String]>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let open_options: Vec<&str> = open_options.iter().map(String::as_str).collect();
+    let allowed_drivers: Option<Vec<&str>> =
+        allowed_drivers.map(|names| names.iter().map(String::as_str).collect());
+    let options = DatasetOptions {
+        open_flags,
+        open_options: Some(&open_options),
+        allowed_drivers: allowed_drivers.as_deref(),
+        ..DatasetOptions::default()
+    };
+
+    let ds = Dataset::open_ex(path, options)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_with_warnings — open_ex, also returning GDAL's warnings
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_with_warnings(
+    path: String,
+    access: Atom,
+    open_options: Vec<String>,
+    allowed_drivers: Option<Vec<String>>,
+) -> NifResult<(Atom, ResourceArc<DatasetResource>, Vec<String>)> {
+    let (result, warnings) = collect_warnings(|| {
+        let open_flags = open_flags(access)?;
+        open_ex(&path, open_flags, &open_options, allowed_drivers.as_deref())
+    });
+    let ds = result.map_err(rustler::Error::from)?;
+    Ok((atoms::ok(), ds, warnings))
+}
+
+/// Runs `f` with a CPL error handler for the current thread that collects
+/// the warnings GDAL emits, which the default handler only prints.
+fn collect_warnings<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    collect_messages(gdal_sys::CPLErr::CE_Warning, f)
+}
+
+/// Runs `f` collecting the messages of class `class` that GDAL emits on the
+/// current thread. Failures are still recorded as the last CPL error, which
+/// is how the NIFs report them, but only the last one is kept there.
+fn collect_messages<T>(class: gdal_sys::CPLErr::Type, f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    /// Pops the handler even if `f` panics, before `collected` is dropped.
+    struct PushedHandler;
+    impl Drop for PushedHandler {
+        fn drop(&mut self) {
+            unsafe { gdal_sys::CPLPopErrorHandler() };
+        }
+    }
+
+    let mut collected = (class, Vec::new());
+    let result = {
+        let user_data = (&mut collected as *mut Collected).cast::<c_void>();
+        unsafe { gdal_sys::CPLPushErrorHandlerEx(Some(push_message), user_data) };
+        let _handler = PushedHandler;
+        f()
+    };
+    (result, collected.1)
+}
+
+/// The message class to collect, and the messages collected so far.
+type Collected = (gdal_sys::CPLErr::Type, Vec<String>);
+
+unsafe extern "C" fn push_message(
+    class: gdal_sys::CPLErr::Type,
+    _number: gdal_sys::CPLErrorNum,
+    message: *const c_char,
+) {
+    let collected = &mut *gdal_sys::CPLGetErrorHandlerUserData().cast::<Collected>();
+    if class == collected.0 && !message.is_null() {
+        collected
+            .1
+            .push(CStr::from_ptr(message).to_string_lossy().into_owned());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_memory — open a dataset from bytes registered under /vsimem
+// ---------------------------------------------------------------------------
+static VSIMEM_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_memory(
+    bytes: Binary,
+    file_name: String,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    // Drivers pick formats by extension, so keep the caller's file name
+    // under a directory unique to this open.
+    let file_name = Path::new(&file_name)
+        .file_name()
+        .ok_or_else(|| Error::invalid_argument("file name is empty"))?;
+    let id = VSIMEM_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = format!("/vsimem/ex_gdal/{id}/{}", file_name.to_string_lossy());
+
+    gdal::vsi::create_mem_file(&path, bytes.as_slice().to_vec())?;
+    match Dataset::open(&path) {
+        Ok(ds) => Ok(ResourceArc::new(DatasetResource::with_vsimem(ds, path))),
+        Err(e) => {
+            let _ = gdal::vsi::unlink_mem_file(&path);
+            Err(e.into())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_vsicurl — open a remote dataset over HTTP range requests
+// ---------------------------------------------------------------------------
+
+/// Thread-local GDAL config options, restored to their previous values when
+/// dropped so they only apply to calls made while the guard is alive.
+struct ScopedConfig(Vec<(String, String)>);
+
+impl ScopedConfig {
+    fn set(options: &HashMap<String, String>) -> Result<Self, Error> {
+        let mut scoped = ScopedConfig(Vec::with_capacity(options.len()));
+        for (key, value) in options {
+            let previous = gdal::config::get_thread_local_config_option(key, "")?;
+            gdal::config::set_thread_local_config_option(key, value)?;
+            scoped.0.push((key.clone(), previous));
+        }
+        Ok(scoped)
+    }
+}
+
+impl Drop for ScopedConfig {
+    fn drop(&mut self) {
+        for (key, previous) in &self.0 {
+            let _ = if previous.is_empty() {
+                gdal::config::clear_thread_local_config_option(key)
+            } else {
+                gdal::config::set_thread_local_config_option(key, previous)
+            };
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_vsicurl(
+    url: String,
+    options: HashMap<String, String>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let path = if url.starts_with("/vsicurl/") {
+        url
+    } else {
+        format!("/vsicurl/{url}")
+    };
+    let _config = ScopedConfig::set(&options)?;
+    let ds = Dataset::open(&path)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_timeout — open with a bound on each HTTP request
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_timeout(path: String, timeout_ms: u64) -> Result<ResourceArc<DatasetResource>, Error> {
+    if timeout_ms == 0 {
+        return Err(Error::invalid_argument("timeout must be positive"));
+    }
+    // GDAL reads both as (fractional) seconds. There is no CPL_CURL_TIMEOUT
+    // config option; these two are what GDAL hands to curl.
+    let seconds = (timeout_ms as f64 / 1000.0).to_string();
+    let options = HashMap::from([
+        ("GDAL_HTTP_TIMEOUT".to_string(), seconds.clone()),
+        ("GDAL_HTTP_CONNECTTIMEOUT".to_string(), seconds),
+    ]);
+    let _config = ScopedConfig::set(&options)?;
+
+    // A driver probing past a timed-out request may fail last with an
+    // unrelated message, so look through every failure of the open.
+    let (result, failures) = collect_messages(gdal_sys::CPLErr::CE_Failure, || {
+        open_ex(&path, GdalOpenFlags::GDAL_OF_READONLY, &[], None)
+    });
+    result.map_err(
+        |e| match failures.into_iter().find(|m| is_curl_timeout(m)) {
+            Some(message) => Error::new(atoms::timeout(), message),
+            None => e,
+        },
+    )
+}
+
+/// GDAL passes curl's error text on as the message of its HTTP failures
+/// (e.g. "CURL error: Operation timed out after 200 milliseconds"), with
+/// no error number of its own for a timeout. "Timeout was reached" is
+/// curl's generic description of the same error.
+fn is_curl_timeout(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("timed out") || message.contains("timeout was reached")
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_threadsafe — several read-only handles for concurrent reads
+// ---------------------------------------------------------------------------
+
+/// Handles opened per thread-safe dataset: the BEAM's default number of
+/// dirty I/O schedulers, the most reads that can run at once.
+const MAX_THREADSAFE_HANDLES: usize = 10;
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_threadsafe(path: String) -> Result<ResourceArc<DatasetResource>, Error> {
+    let handles = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_THREADSAFE_HANDLES);
+    let readers = (1..handles)
+        .map(|_| Dataset::open(&path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let ds = Dataset::open(&path)?;
+    Ok(ResourceArc::new(DatasetResource::with_readers(ds, readers)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_zip / list_zip — datasets inside a zip archive, via /vsizip/
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_zip(
+    archive: String,
+    inner_path: String,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let path = format!("/vsizip/{archive}/{}", inner_path.trim_start_matches('/'));
+    open_ex(&path, GdalOpenFlags::GDAL_OF_READONLY, &[], None)
+}
+
+/// Lists the archive's files that a raster driver recognises, as paths
+/// inside the archive; directories and other files are skipped.
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_list_zip(archive: String) -> Result<Vec<String>, Error> {
+    let root = format!("/vsizip/{archive}");
+    let entries = gdal::vsi::read_dir(&root, true).map_err(|_| {
+        Error::new(
+            atoms::open_failed(),
+            format!("cannot read zip archive {archive}"),
+        )
+    })?;
+
+    let mut rasters = Vec::new();
+    for entry in entries {
+        let entry = entry.to_string_lossy().into_owned();
+        // VSIReadDirRecursive marks directories with a trailing slash
+        if entry.ends_with('/') {
+            continue;
+        }
+        let c_path = CString::new(format!("{root}/{entry}"))
+            .map_err(|_| Error::invalid_argument("path contains a NUL byte"))?;
+        let driver = unsafe {
+            gdal_sys::GDALIdentifyDriverEx(
+                c_path.as_ptr(),
+                GdalOpenFlags::GDAL_OF_RASTER.bits(),
+                std::ptr::null(),
+                std::ptr::null(),
+            )
+        };
+        if !driver.is_null() {
+            rasters.push(entry);
+        }
+    }
+    Ok(rasters)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_config_option / get_config_option — process-wide GDAL config
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_config_option(key: String, value: Option<String>) -> NifResult<Atom> {
+    let result = match value {
+        Some(value) => gdal::config::set_config_option(&key, &value),
+        None => gdal::config::clear_config_option(&key),
+    };
+    unit_result(result.map_err(Error::from))
+}
+
+#[rustler::nif]
+fn gdal_get_config_option(key: String) -> Result<Option<String>, Error> {
+    let value = gdal::config::get_config_option(&key, "")?;
+    Ok(Some(value).filter(|v| !v.is_empty()))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_cache_max / get_cache_used — the process-wide block cache
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_cache_max(bytes: u64) -> NifResult<Atom> {
+    let bytes = i64::try_from(bytes)
+        .map_err(|_| Error::invalid_argument(format!("cache size {bytes} is too large")));
+    unit_result(bytes.map(|bytes| unsafe { gdal_sys::GDALSetCacheMax64(bytes) }))
+}
+
+#[rustler::nif]
+fn gdal_get_cache_used() -> u64 {
+    let used = unsafe { gdal_sys::GDALGetCacheUsed64() };
+    u64::try_from(used).unwrap_or(0)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: create — new dataset from a driver short name and a band data type
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_create(
+    driver_name: String,
+    path: String,
+    width: usize,
+    height: usize,
+    band_count: usize,
+    data_type: Atom,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let data_type = atom_to_data_type(data_type)?;
+    let driver = driver_by_name(&driver_name)?;
+    if !has_capability(&driver, "DCAP_CREATE") {
+        return Err(Error::invalid_argument(format!(
+            "driver {driver_name} does not support creation"
+        )));
+    }
+
+    let (w, h, n) = (width, height, band_count);
+    let ds = match data_type {
+        GdalDataType::UInt8 => driver.create_with_band_type::<u8, _>(&path, w, h, n),
+        GdalDataType::Int16 => driver.create_with_band_type::<i16, _>(&path, w, h, n),
+        GdalDataType::UInt16 => driver.create_with_band_type::<u16, _>(&path, w, h, n),
+        GdalDataType::Int32 => driver.create_with_band_type::<i32, _>(&path, w, h, n),
+        GdalDataType::UInt32 => driver.create_with_band_type::<u32, _>(&path, w, h, n),
+        GdalDataType::Float32 => driver.create_with_band_type::<f32, _>(&path, w, h, n),
+        _ => driver.create_with_band_type::<f64, _>(&path, w, h, n),
+    }?;
+
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: create_vector — new empty vector dataset for layers to be added to
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_create_vector(
+    driver_name: String,
+    path: String,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let driver = driver_by_name(&driver_name)?;
+    if !has_capability(&driver, "DCAP_VECTOR") || !has_capability(&driver, "DCAP_CREATE") {
+        return Err(Error::invalid_argument(format!(
+            "driver {driver_name} does not support vector creation"
+        )));
+    }
+
+    let ds = driver.create_vector_only(&path)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: copy — write the dataset out with another driver and creation options
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_copy(
+    resource: ResourceArc<DatasetResource>,
+    driver_name: String,
+    path: String,
+    creation_options: Vec<(String, String)>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let driver = driver_by_name(&driver_name)?;
+    if !has_capability(&driver, "DCAP_CREATECOPY") && !has_capability(&driver, "DCAP_CREATE") {
+        return Err(Error::invalid_argument(format!(
+            "driver {driver_name} does not support creating copies"
+        )));
+    }
+
+    let mut options = CslStringList::new();
+    for (name, value) in &creation_options {
+        options.set_name_value(name, value)?;
+    }
+
+    let ds = resource.lock()?.create_copy(&driver, &path, &options)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: create_copy_progress — copy, sending {:progress, fraction} to a pid
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_create_copy_progress(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    driver_name: String,
+    path: String,
+    creation_options: Vec<(String, String)>,
+    pid: LocalPid,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let driver = driver_by_name(&driver_name)?;
+    if !has_capability(&driver, "DCAP_CREATECOPY") && !has_capability(&driver, "DCAP_CREATE") {
+        return Err(Error::invalid_argument(format!(
+            "driver {driver_name} does not support creating copies"
+        )));
+    }
+
+    let mut options = CslStringList::new();
+    for (name, value) in &creation_options {
+        options.set_name_value(name, value)?;
+    }
+    let c_path =
+        CString::new(path).map_err(|_| Error::invalid_argument("path contains a NUL byte"))?;
+
+    let src = resource.lock()?;
+    let progress = CopyProgress {
+        env,
+        pid,
+        last_sent: Mutex::new(-1.0),
+    };
+    let copy = unsafe {
+        gdal_sys::GDALCreateCopy(
+            driver.c_driver(),
+            c_path.as_ptr(),
+            src.c_dataset(),
+            0,
+            options.as_ptr(),
+            Some(send_copy_progress),
+            (&progress as *const CopyProgress)
+                .cast_mut()
+                .cast::<c_void>(),
+        )
+    };
+    if copy.is_null() {
+        return Err(last_cpl_error("GDALCreateCopy"));
+    }
+    let copy = unsafe { Dataset::from_c_dataset(copy) };
+
+    let _ = env.send(&pid, atoms::done());
+    Ok(ResourceArc::new(DatasetResource::new(copy)))
+}
+
+/// Where `send_copy_progress` reports to, and the last fraction it sent.
+/// Drivers may report from several worker threads at once.
+struct CopyProgress<'a> {
+    env: Env<'a>,
+    pid: LocalPid,
+    last_sent: Mutex<f64>,
+}
+
+/// GDAL progress callback sending `{:progress, fraction}` at most once per
+/// percent. Never cancels the copy.
+unsafe extern "C" fn send_copy_progress(
+    complete: f64,
+    _message: *const c_char,
+    arg: *mut c_void,
+) -> c_int {
+    let progress = &*arg.cast::<CopyProgress>();
+    // Held while sending, so fractions from different threads stay in order.
+    let mut last_sent = lock_recovering(&progress.last_sent);
+    let due = complete > *last_sent && (complete >= 1.0 || complete - *last_sent >= 0.01);
+    if !due {
+        return 1;
+    }
+    *last_sent = complete;
+    let message = (atoms::progress(), complete);
+    // The NIF's env can only send from the dirty scheduler thread running
+    // the copy; driver worker threads are not managed by the VM and need
+    // an env of their own.
+    if rustler::thread::is_scheduler_thread() {
+        let _ = progress.env.send(&progress.pid, message);
+    } else {
+        let _ = OwnedEnv::new().send_and_clear(&progress.pid, |env| message.encode(env));
+    }
+    1
+}
+
+// ---------------------------------------------------------------------------
+// NIF: to_mem — an editable in-RAM copy made with the MEM driver
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_to_mem(
+    resource: ResourceArc<DatasetResource>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let driver = driver_by_name("MEM")?;
+    let ds = resource
+        .lock()?
+        .create_copy(&driver, "", &CslStringList::new())?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: close — drop the GDAL dataset now instead of waiting for GC
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_close(resource: ResourceArc<DatasetResource>) -> Atom {
+    let mut inner = lock_recovering(&resource.inner);
+    resource.release(&mut inner);
+    atoms::ok()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: raster_count
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_raster_count(resource: ResourceArc<DatasetResource>) -> Result<usize, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.raster_count())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: raster_size
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_raster_size(resource: ResourceArc<DatasetResource>) -> Result<(usize, usize), Error> {
+    let ds = resource.lock()?;
+    Ok(ds.raster_size())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: dimensions — raster_size with labeled width and height
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct Dimensions {
+    width: usize,
+    height: usize,
+}
+
+#[rustler::nif]
+fn gdal_dimensions(resource: ResourceArc<DatasetResource>) -> Result<Dimensions, Error> {
+    let ds = resource.lock()?;
+    let (width, height) = ds.raster_size();
+    Ok(Dimensions { width, height })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_size — a band's own {x, y}, which may differ from raster_size
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_size(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<(usize, usize), Error> {
+    let ds = resource.lock()?;
+    Ok(raster_band(&ds, band_idx)?.size())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band (full band as raw u8 bytes)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let size = band.size();
+    let bytes = read_window_bytes(&band, (0, 0), size)?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_resampled — full band decimated or enlarged by GDAL
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_resampled(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    width: usize,
+    height: usize,
+    resampling: Atom,
+) -> Result<Binary, Error> {
+    let resample = atom_to_resample_alg(resampling)?;
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let bytes = read_bytes(
+        &band,
+        ReadSpec {
+            window: (0, 0),
+            window_size: band.size(),
+            buffer_size: (width, height),
+            resample: Some(resample),
+        },
+    )?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+fn atom_to_resample_alg(atom: Atom) -> Result<ResampleAlg, Error> {
+    let alg = match atom {
+        a if a == atoms::nearest() => ResampleAlg::NearestNeighbour,
+        a if a == atoms::bilinear() => ResampleAlg::Bilinear,
+        a if a == atoms::cubic() => ResampleAlg::Cubic,
+        a if a == atoms::average() => ResampleAlg::Average,
+        _ => return Err(Error::invalid_argument("unsupported resampling algorithm")),
+    };
+    Ok(alg)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_typed — full band plus the type and shape Nx needs
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct TypedBand<'a> {
+    data: Binary<'a>,
+    r#type: Atom,
+    shape: (usize, usize),
+    endianness: Atom,
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_typed<'a>(
+    env: Env<'a>,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<TypedBand<'a>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let (width, height) = band.size();
+    let bytes = read_window_bytes(&band, (0, 0), (width, height))?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(TypedBand {
+        data: binary.into(),
+        r#type: nx_type(band.band_type()),
+        shape: (height, width),
+        endianness: native_endianness(),
+    })
+}
+
+/// Nx type of the elements `read_window_bytes` returns for a band type.
+fn nx_type(dt: GdalDataType) -> Atom {
+    match read_type(dt) {
+        GdalDataType::UInt8 => atoms::u8(),
+        GdalDataType::Int16 => atoms::s16(),
+        GdalDataType::UInt16 => atoms::u16(),
+        GdalDataType::Int32 => atoms::s32(),
+        GdalDataType::UInt32 => atoms::u32(),
+        GdalDataType::Float32 => atoms::f32(),
+        _ => atoms::f64(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: nx_type — the band's Nx type tuple written out, e.g. "{:f, 32}"
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_nx_type(resource: ResourceArc<DatasetResource>, band_idx: usize) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let dt = raster_band(&ds, band_idx)?.band_type();
+    Ok(nx_type_spec(dt).to_string())
+}
+
+/// `nx_type` in Nx's `{kind, bits}` notation; the two must agree.
+fn nx_type_spec(dt: GdalDataType) -> &'static str {
+    match read_type(dt) {
+        GdalDataType::UInt8 => "{:u, 8}",
+        GdalDataType::Int16 => "{:s, 16}",
+        GdalDataType::UInt16 => "{:u, 16}",
+        GdalDataType::Int32 => "{:s, 32}",
+        GdalDataType::UInt32 => "{:u, 32}",
+        GdalDataType::Float32 => "{:f, 32}",
+        _ => "{:f, 64}",
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_rows — small bands as nested lists of Elixir numbers
+// ---------------------------------------------------------------------------
+
+/// Largest band `gdal_read_band_rows` and `gdal_read_band_list` will
+/// decode; every pixel becomes a separate term, so larger bands should use
+/// the binary readers.
+const MAX_ROWS_PIXELS: usize = 1 << 20;
+
+/// Floats are `None` (`nil`) for NaN, which has no Erlang float term.
+#[derive(NifUntaggedEnum)]
+enum BandRows {
+    Integer(Vec<Vec<i64>>),
+    Float(Vec<Vec<Option<f64>>>),
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_rows(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<BandRows, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let size = check_term_pixels(&band, "read_band_rows")?;
+    let rows = match band.band_type() {
+        GdalDataType::Float32 | GdalDataType::Float64 => {
+            BandRows::Float(read_rows(&band, size, |v: f64| (!v.is_nan()).then_some(v))?)
+        }
+        _ => BandRows::Integer(read_rows(&band, size, |v: i64| v)?),
+    };
+    Ok(rows)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_list — small bands as one flat list of Elixir numbers
+// ---------------------------------------------------------------------------
+
+/// Floats are `None` (`nil`) for NaN, as in `BandRows`.
+#[derive(NifUntaggedEnum)]
+enum BandValues {
+    Integer(Vec<i64>),
+    Float(Vec<Option<f64>>),
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_list(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<(Atom, BandValues), Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let size = check_term_pixels(&band, "read_band_list")?;
+    let dt = band.band_type();
+    let values = match dt {
+        GdalDataType::Float32 | GdalDataType::Float64 => {
+            let buf = band.read_as::<f64>((0, 0), size, size, None)?;
+            BandValues::Float(
+                buf.data()
+                    .iter()
+                    .map(|&v| (!v.is_nan()).then_some(v))
+                    .collect(),
+            )
+        }
+        _ => {
+            let buf = band.read_as::<i64>((0, 0), size, size, None)?;
+            BandValues::Integer(buf.into_shape_and_vec().1)
+        }
+    };
+    Ok((data_type_to_atom(dt), values))
+}
+
+/// The band's size, if it is small enough for `reader` to decode into one
+/// term per pixel.
+fn check_term_pixels(band: &RasterBand, reader: &str) -> Result<(usize, usize), Error> {
+    let (width, height) = band.size();
+    let pixels = width * height;
+    if pixels > MAX_ROWS_PIXELS {
+        return Err(Error::invalid_argument(format!(
+            "band has {pixels} pixels, {reader} is limited to {MAX_ROWS_PIXELS}"
+        )));
+    }
+    Ok((width, height))
+}
+
+fn read_rows<T: Copy + GdalType, U>(
+    band: &RasterBand,
+    size: (usize, usize),
+    convert: impl Fn(T) -> U,
+) -> gdal::errors::Result<Vec<Vec<U>>> {
+    let buf = band.read_as::<T>((0, 0), size, size, None)?;
+    Ok(buf
+        .data()
+        .chunks(size.0.max(1))
+        .map(|row| row.iter().map(|&v| convert(v)).collect())
+        .collect())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_pixel — one physical value, nil for nodata
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_pixel(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    col: isize,
+    row: isize,
+) -> Result<Option<f64>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let sampler = PixelSampler::new(&band);
+    if !sampler.contains(col, row) {
+        let (width, height) = sampler.size;
+        return Err(Error::invalid_argument(format!(
+            "pixel {{{col}, {row}}} is outside the {width}x{height} band"
+        )));
+    }
+    Ok(sampler.value_at(col, row)?)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: sample_points — physical values at many pixels under a single lock
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_sample_points(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    points: Vec<(isize, isize)>,
+) -> Result<Vec<Option<f64>>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let sampler = PixelSampler::new(&band);
+    points
+        .iter()
+        .map(|&(col, row)| {
+            if !sampler.contains(col, row) {
+                return Ok(None);
+            }
+            Ok(sampler.value_at(col, row)?)
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: sample_coords — physical values at georeferenced points
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_sample_coords(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    coords: Vec<(f64, f64)>,
+    interpolation: Atom,
+) -> Result<Vec<Option<f64>>, Error> {
+    let bilinear = match interpolation {
+        a if a == atoms::nearest() => false,
+        a if a == atoms::bilinear() => true,
+        _ => {
+            return Err(Error::invalid_argument(
+                "interpolation must be :nearest or :bilinear",
+            ))
+        }
+    };
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let inverse = ds.geo_transform()?.invert()?;
+    let sampler = PixelSampler::new(&band);
+    coords
+        .iter()
+        .map(|&(x, y)| {
+            // The pixel containing the point; its edges floor into it.
+            let (col, row) = inverse.apply(x, y);
+            if !col.is_finite() || !row.is_finite() {
+                return Ok(None);
+            }
+            let pixel = (col.floor() as isize, row.floor() as isize);
+            if !sampler.contains(pixel.0, pixel.1) {
+                return Ok(None);
+            }
+            if bilinear {
+                Ok(sampler.bilinear_at(col, row)?)
+            } else {
+                Ok(sampler.value_at(pixel.0, pixel.1)?)
+            }
+        })
+        .collect()
+}
+
+/// Reads single pixels of a band as physical values, `raw * scale + offset`,
+/// with `None` for nodata and NaN.
+struct PixelSampler<'a> {
+    band: &'a RasterBand<'a>,
+    size: (usize, usize),
+    no_data: Option<f64>,
+    scale: f64,
+    offset: f64,
+}
+
+impl<'a> PixelSampler<'a> {
+    fn new(band: &'a RasterBand<'a>) -> Self {
+        PixelSampler {
+            band,
+            size: band.size(),
+            no_data: band.no_data_value(),
+            scale: band.scale().unwrap_or(1.0),
+            offset: band.offset().unwrap_or(0.0),
+        }
+    }
+
+    fn contains(&self, col: isize, row: isize) -> bool {
+        let (width, height) = self.size;
+        col >= 0 && row >= 0 && (col as usize) < width && (row as usize) < height
+    }
+
+    /// The pixel at `col`, `row`, which must be inside the band.
+    fn value_at(&self, col: isize, row: isize) -> gdal::errors::Result<Option<f64>> {
+        let buf = self.band.read_as::<f64>((col, row), (1, 1), (1, 1), None)?;
+        Ok(self.physical(buf.data()[0]))
+    }
+
+    /// Interpolates between the four pixel centres around fractional `col`,
+    /// `row`, which must be inside the band. Neighbours past the edge repeat
+    /// the edge pixel; any nodata neighbour makes the result `None`.
+    fn bilinear_at(&self, col: f64, row: f64) -> gdal::errors::Result<Option<f64>> {
+        let (width, height) = self.size;
+        let (u, v) = (col - 0.5, row - 0.5);
+        let (c0, r0) = (u.floor(), v.floor());
+        let (fx, fy) = (u - c0, v - r0);
+        let clamp = |i: f64, len: usize| (i.max(0.0) as isize).min(len as isize - 1);
+        let cols = [clamp(c0, width), clamp(c0 + 1.0, width)];
+        let rows = [clamp(r0, height), clamp(r0 + 1.0, height)];
+
+        let mut corners = [[0.0; 2]; 2];
+        for (j, &r) in rows.iter().enumerate() {
+            for (i, &c) in cols.iter().enumerate() {
+                match self.value_at(c, r)? {
+                    Some(value) => corners[j][i] = value,
+                    None => return Ok(None),
+                }
+            }
+        }
+        let top = corners[0][0] * (1.0 - fx) + corners[0][1] * fx;
+        let bottom = corners[1][0] * (1.0 - fx) + corners[1][1] * fx;
+        Ok(Some(top * (1.0 - fy) + bottom * fy))
+    }
+
+    fn physical(&self, raw: f64) -> Option<f64> {
+        if raw.is_nan() || self.no_data == Some(raw) {
+            None
+        } else {
+            Some(raw * self.scale + self.offset)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_mask_band — per-pixel validity mask of a band, 0 or 255 per pixel
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_mask_band(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let mask = band.open_mask_band()?;
+    let size = mask.size();
+    let bytes = read_as_bytes(&mask, ReadSpec::window((0, 0), size), u8::to_ne_bytes)?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: mask_flags — how a band's mask is derived
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct MaskFlags {
+    all_valid: bool,
+    per_dataset: bool,
+    alpha: bool,
+    nodata: bool,
+}
+
+#[rustler::nif]
+fn gdal_mask_flags(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<MaskFlags, Error> {
+    let ds = resource.lock()?;
+    let flags = raster_band(&ds, band_idx)?.mask_flags()?;
+    Ok(MaskFlags {
+        all_valid: flags.is_all_valid(),
+        per_dataset: flags.is_per_dataset(),
+        alpha: flags.is_alpha(),
+        nodata: flags.is_nodata(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_bands (several full bands, pixel-interleaved, as one binary)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_bands<'a>(
+    env: Env<'a>,
+    resource: ResourceArc<DatasetResource>,
+    band_indices: Vec<usize>,
+) -> Result<(Binary<'a>, usize), Error> {
+    if band_indices.is_empty() {
+        return Err(Error::invalid_argument("band index list is empty"));
+    }
+    let ds = resource.lock()?;
+
+    let mut bands = Vec::with_capacity(band_indices.len());
+    for &idx in &band_indices {
+        bands.push(raster_band(&ds, idx)?);
+    }
+    let band_type = bands[0].band_type();
+    if bands.iter().any(|b| b.band_type() != band_type) {
+        return Err(Error::invalid_argument(
+            "bands must share one data type to be interleaved",
+        ));
+    }
+    let size = bands[0].size();
+    if bands.iter().any(|b| b.size() != size) {
+        return Err(Error::invalid_argument(
+            "bands must share one size to be interleaved",
+        ));
+    }
+
+    let pixels = size.0 * size.1;
+    let mut planes = Vec::with_capacity(bands.len());
+    for band in &bands {
+        planes.push(read_window_bytes(band, (0, 0), size)?);
+    }
+
+    // Band-sequential planes to band-interleaved-by-pixel
+    let mut binary = NewBinary::new(env, planes.len() * planes[0].len());
+    if let Some(width) = planes[0].len().checked_div(pixels) {
+        let out = binary.as_mut_slice();
+        for (b, plane) in planes.iter().enumerate() {
+            for (i, element) in plane.chunks_exact(width).enumerate() {
+                let offset = (i * planes.len() + b) * width;
+                out[offset..offset + width].copy_from_slice(element);
+            }
+        }
+    }
+    Ok((binary.into(), pixels * planes.len()))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_rgba — color bands plus alpha or mask as packed RGBA8
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_rgba(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    window: Option<(isize, isize, usize, usize)>,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let count = ds.raster_count();
+
+    let mut interps = Vec::with_capacity(count);
+    for idx in 1..=count {
+        interps.push(raster_band(&ds, idx)?.color_interpretation());
+    }
+    // First band carrying the interpretation, 1-based
+    let find =
+        |interp: ColorInterpretation| interps.iter().position(|i| *i == interp).map(|p| p + 1);
+    let color = [
+        find(ColorInterpretation::RedBand),
+        find(ColorInterpretation::GreenBand),
+        find(ColorInterpretation::BlueBand),
+    ];
+    let color = match color {
+        [Some(r), Some(g), Some(b)] => [r, g, b],
+        _ if count >= 3 => [1, 2, 3],
+        _ => {
+            return Err(Error::invalid_argument(format!(
+                "RGBA needs three color bands, dataset has {count}"
+            )))
+        }
+    };
+
+    let (x, y, w, h) = window.unwrap_or_else(|| {
+        let (width, height) = ds.raster_size();
+        (0, 0, width, height)
+    });
+    let spec = ReadSpec::window((x, y), (w, h));
+    let mut planes = Vec::with_capacity(4);
+    for &idx in &color {
+        let band = raster_band(&ds, idx)?;
+        planes.push(read_as_bytes(&band, spec, u8::to_ne_bytes)?);
+    }
+
+    // An alpha band is used as is; otherwise the red band's mask (nodata or
+    // per-dataset) decides, with any non-zero value fully opaque.
+    let alpha = match find(ColorInterpretation::AlphaBand) {
+        Some(idx) => read_as_bytes(&raster_band(&ds, idx)?, spec, u8::to_ne_bytes)?,
+        None => {
+            let red = raster_band(&ds, color[0])?;
+            let mask = red.open_mask_band()?;
+            let mut bytes = read_as_bytes(&mask, spec, u8::to_ne_bytes)?;
+            for v in &mut bytes {
+                *v = if *v == 0 { 0 } else { 255 };
+            }
+            bytes
+        }
+    };
+    planes.push(alpha);
+
+    let mut binary = NewBinary::new(env, w * h * 4);
+    for (i, pixel) in binary.as_mut_slice().chunks_exact_mut(4).enumerate() {
+        for (out, plane) in pixel.iter_mut().zip(&planes) {
+            *out = plane[i];
+        }
+    }
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_le (full band as little-endian bytes, regardless of host)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_le(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let size = band.size();
+    let mut bytes = read_window_bytes(&band, (0, 0), size)?;
+    native_to_le(&mut bytes, size.0 * size.1);
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_as_float — physical values as little-endian f32, NaN nodata
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_as_float(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    // Compare in f32, the type GDAL converts the pixels to
+    let no_data = band.no_data_value().map(|v| v as f32);
+    read_physical_f32_le(env, &band, no_data)
+}
+
+/// Reads the band as f32 and writes `raw * scale + offset` per pixel as
+/// little-endian bytes, or NaN where the raw value equals `nan_for`.
+fn read_physical_f32_le<'a>(
+    env: Env<'a>,
+    band: &RasterBand,
+    nan_for: Option<f32>,
+) -> Result<Binary<'a>, Error> {
+    let size = band.size();
+    let buffer = band.read_as::<f32>((0, 0), size, size, None)?;
+    let scale = band.scale().unwrap_or(1.0);
+    let offset = band.offset().unwrap_or(0.0);
+
+    let mut binary = NewBinary::new(env, std::mem::size_of_val(buffer.data()));
+    let out = binary.as_mut_slice().chunks_exact_mut(4);
+    for (chunk, &raw) in out.zip(buffer.data()) {
+        let value = if nan_for == Some(raw) {
+            f32::NAN
+        } else {
+            (f64::from(raw) * scale + offset) as f32
+        };
+        chunk.copy_from_slice(&value.to_le_bytes());
+    }
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_masked — physical f32 values plus a 0/255 validity mask
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct MaskedBand<'a> {
+    data: Binary<'a>,
+    mask: Binary<'a>,
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_masked(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<MaskedBand, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let data = read_physical_f32_le(env, &band, None)?;
+
+    // GDAL's mask band covers nodata, alpha and per-dataset masks alike;
+    // alpha masks may hold partial values, so any non-zero is valid.
+    let mask_band = band.open_mask_band()?;
+    let size = mask_band.size();
+    let validity = mask_band.read_as::<u8>((0, 0), size, size, None)?;
+    let mut mask = NewBinary::new(env, validity.data().len());
+    for (out, &v) in mask.as_mut_slice().iter_mut().zip(validity.data()) {
+        *out = if v == 0 { 0 } else { 255 };
+    }
+
+    Ok(MaskedBand {
+        data,
+        mask: mask.into(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: native_endianness — byte order of the native-endian readers
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_native_endianness() -> Atom {
+    native_endianness()
+}
+
+fn native_endianness() -> Atom {
+    if cfg!(target_endian = "little") {
+        atoms::little()
+    } else {
+        atoms::big()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: version — the linked GDAL's GDALVersionInfo
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct GdalVersion {
+    release_name: String,
+    version_num: u32,
+    build_info: HashMap<String, String>,
+}
+
+#[rustler::nif]
+fn gdal_version() -> GdalVersion {
+    GdalVersion {
+        release_name: VersionInfo::release_name(),
+        version_num: VersionInfo::version_num().parse().unwrap_or_default(),
+        build_info: VersionInfo::build_info(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_window (sub-region as raw native-endian bytes)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_band_window(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    x: isize,
+    y: isize,
+    w: usize,
+    h: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+
+    let bytes = read_window_bytes(&band, (x, y), (w, h))?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_windows — several windows of one band under a single lock
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_windows<'a>(
+    env: Env<'a>,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    windows: Vec<(isize, isize, usize, usize)>,
+) -> Result<Vec<Binary<'a>>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let (width, height) = band.size();
+
+    windows
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y, w, h))| {
+            if !window_inside((x, y, w, h), (width, height)) {
+                return Err(Error::invalid_argument(format!(
+                    "window {i} {{{x}, {y}, {w}, {h}}} is outside the {width}x{height} band"
+                )));
+            }
+            let bytes = read_window_bytes(&band, (x, y), (w, h)).map_err(|e| {
+                let e = Error::from(e);
+                Error::new(e.kind, format!("window {i}: {}", e.message))
+            })?;
+
+            let mut binary = NewBinary::new(env, bytes.len());
+            binary.as_mut_slice().copy_from_slice(&bytes);
+            Ok(binary.into())
+        })
+        .collect()
+}
+
+/// Whether the `{x, y, w, h}` window lies inside a band of `size`. The
+/// window is caller input, so the end offsets are checked for overflow
+/// rather than allowed to wrap past the bounds check.
+fn window_inside(window: (isize, isize, usize, usize), size: (usize, usize)) -> bool {
+    let (x, y, w, h) = window;
+    let fits = |start: isize, len: usize, full: usize| {
+        usize::try_from(start)
+            .is_ok_and(|start| start.checked_add(len).is_some_and(|end| end <= full))
+    };
+    fits(x, w, size.0) && fits(y, h, size.1)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band — a reusable handle to one band, and reads through it
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<ResourceArc<BandResource>, Error> {
+    let c_band = {
+        let ds = resource.lock_primary()?;
+        let band = raster_band(&ds, band_idx)?;
+        unsafe { band.c_rasterband() }
+    };
+    Ok(ResourceArc::new(BandResource {
+        dataset: resource,
+        c_band: SendBandHandle(c_band),
+    }))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_read(env: Env, band: ResourceArc<BandResource>) -> Result<Binary, Error> {
+    let bytes = band.with_band(|band| Ok(read_window_bytes(band, (0, 0), band.size())?))?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_read_window(
+    env: Env,
+    band: ResourceArc<BandResource>,
+    window: (isize, isize, usize, usize),
+) -> Result<Binary, Error> {
+    let (x, y, w, h) = window;
+    let bytes = band.with_band(|band| Ok(read_window_bytes(band, (x, y), (w, h))?))?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_band_window_resampled — sub-region resampled to an output size
+// ---------------------------------------------------------------------------
+/// Reads through a band handle from `gdal_band`, like `gdal_band_read_window`.
+#[rustler::nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+fn gdal_read_band_window_resampled(
+    env: Env,
+    band: ResourceArc<BandResource>,
+    x: isize,
+    y: isize,
+    w: usize,
+    h: usize,
+    out_w: usize,
+    out_h: usize,
+    resampling: Atom,
+) -> Result<Binary, Error> {
+    let resample = atom_to_resample_alg(resampling)?;
+    let bytes = band.with_band(|band| {
+        let read = ReadSpec {
+            window: (x, y),
+            window_size: (w, h),
+            buffer_size: (out_w, out_h),
+            resample: Some(resample),
+        };
+        Ok(read_bytes(band, read)?)
+    })?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+/// Reads a window of `band` in its native data type and flattens it to
+/// native-endian bytes, one element's worth of bytes per pixel.
+fn read_window_bytes(
+    band: &RasterBand,
+    window: (isize, isize),
+    window_size: (usize, usize),
+) -> gdal::errors::Result<Vec<u8>> {
+    read_bytes(band, ReadSpec::window(window, window_size))
+}
+
+/// Like `read_window_bytes`, but GDAL resamples the window to
+/// `read.buffer_size` pixels when that differs from its size.
+fn read_bytes(band: &RasterBand, read: ReadSpec) -> gdal::errors::Result<Vec<u8>> {
+    match read_type(band.band_type()) {
+        GdalDataType::UInt8 => read_as_bytes(band, read, u8::to_ne_bytes),
+        GdalDataType::Int16 => read_as_bytes(band, read, i16::to_ne_bytes),
+        GdalDataType::UInt16 => read_as_bytes(band, read, u16::to_ne_bytes),
+        GdalDataType::Int32 => read_as_bytes(band, read, i32::to_ne_bytes),
+        GdalDataType::UInt32 => read_as_bytes(band, read, u32::to_ne_bytes),
+        GdalDataType::Float32 => read_as_bytes(band, read, f32::to_ne_bytes),
+        _ => read_as_bytes(band, read, f64::to_ne_bytes),
+    }
+}
+
+/// The type `read_bytes` returns a band's pixels in: its own type, or f64
+/// for types without a native reader (Int8, Int64, UInt64, complex, ...).
+/// Everything describing the readers' bytes must go through this.
+fn read_type(dt: GdalDataType) -> GdalDataType {
+    match dt {
+        GdalDataType::UInt8
+        | GdalDataType::Int16
+        | GdalDataType::UInt16
+        | GdalDataType::Int32
+        | GdalDataType::UInt32
+        | GdalDataType::Float32 => dt,
+        _ => GdalDataType::Float64,
+    }
+}
+
+/// Arguments of a `RasterBand::read_as` call; `resample` defaults to
+/// nearest neighbour when `None`.
+#[derive(Clone, Copy)]
+struct ReadSpec {
+    window: (isize, isize),
+    window_size: (usize, usize),
+    buffer_size: (usize, usize),
+    resample: Option<ResampleAlg>,
+}
+
+impl ReadSpec {
+    /// A full-resolution read of `window_size` pixels at `window`.
+    fn window(window: (isize, isize), window_size: (usize, usize)) -> Self {
+        ReadSpec {
+            window,
+            window_size,
+            buffer_size: window_size,
+            resample: None,
+        }
+    }
+}
+
+fn read_as_bytes<T: Copy + GdalType, const N: usize>(
+    band: &RasterBand,
+    read: ReadSpec,
+    to_bytes: fn(T) -> [u8; N],
+) -> gdal::errors::Result<Vec<u8>> {
+    let buf = band.read_as::<T>(
+        read.window,
+        read.window_size,
+        read.buffer_size,
+        read.resample,
+    )?;
+    Ok(buf.data().iter().flat_map(|&v| to_bytes(v)).collect())
+}
+
+/// Reorders native-endian `bytes` holding `pixels` equally-sized elements so
+/// that each element is little-endian. A no-op on little-endian hosts.
+fn native_to_le(bytes: &mut [u8], pixels: usize) {
+    if cfg!(target_endian = "big") && pixels > 0 {
+        let width = bytes.len() / pixels;
+        for element in bytes.chunks_exact_mut(width) {
+            element.reverse();
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: raster_io — GDALRasterIO with explicit pixel and line spacing
+// ---------------------------------------------------------------------------
+/// Reads through a band handle from `gdal_band`, like `gdal_band_read_window`.
+#[rustler::nif(schedule = "DirtyIo")]
+#[allow(clippy::too_many_arguments)]
+fn gdal_raster_io(
+    env: Env,
+    band: ResourceArc<BandResource>,
+    x: isize,
+    y: isize,
+    w: usize,
+    h: usize,
+    out_w: usize,
+    out_h: usize,
+    pixel_space: usize,
+    line_space: usize,
+) -> Result<Binary, Error> {
+    band.with_band(|band| {
+        let type_size = band.band_type().bytes() as usize;
+        // All three are caller input; an overflow here would size the buffer
+        // smaller than what GDAL writes into it.
+        let too_large = || Error::invalid_argument("output size or spacing too large");
+        let pixel_space = match pixel_space {
+            0 => type_size,
+            pixel_space => pixel_space,
+        };
+        let row_bytes = pixel_space.checked_mul(out_w).ok_or_else(too_large)?;
+        let line_space = match line_space {
+            0 => row_bytes,
+            line_space => line_space,
+        };
+        if pixel_space < type_size || line_space < row_bytes {
+            return Err(Error::invalid_argument(format!(
+                "spacing {{{pixel_space}, {line_space}}} overlaps pixels of {type_size} bytes \
+                 across {out_w} columns"
+            )));
+        }
+
+        let len = match (out_w, out_h) {
+            (0, _) | (_, 0) => 0,
+            _ => (out_h - 1)
+                .checked_mul(line_space)
+                .and_then(|n| n.checked_add((out_w - 1) * pixel_space))
+                .and_then(|n| n.checked_add(type_size))
+                .ok_or_else(too_large)?,
+        };
+        let (x, y, w, h) = (to_c_int(x)?, to_c_int(y)?, to_c_int(w)?, to_c_int(h)?);
+        let (buf_w, buf_h) = (to_c_int(out_w)?, to_c_int(out_h)?);
+        let (pixel_space, line_space) = (to_c_int(pixel_space)?, to_c_int(line_space)?);
+
+        let mut binary = NewBinary::new(env, len);
+        let buf = binary.as_mut_slice();
+        // Bytes between pixels are not written by GDAL.
+        buf.fill(0);
+        let rv = unsafe {
+            gdal_sys::GDALRasterIO(
+                band.c_rasterband(),
+                gdal_sys::GDALRWFlag::GF_Read,
+                x,
+                y,
+                w,
+                h,
+                buf.as_mut_ptr().cast(),
+                buf_w,
+                buf_h,
+                gdal_sys::GDALGetRasterDataType(band.c_rasterband()),
+                pixel_space,
+                line_space,
+            )
+        };
+        if rv != gdal_sys::CPLErr::CE_None {
+            return Err(last_cpl_error("GDALRasterIO"));
+        }
+        Ok(binary.into())
+    })
+}
+
+fn to_c_int<T: TryInto<c_int>>(value: T) -> Result<c_int, Error> {
+    value
+        .try_into()
+        .map_err(|_| Error::invalid_argument("window, size or spacing out of range"))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: block_size — natural block (tile or strip) dimensions of a band
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_block_size(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<(usize, usize), Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.block_size())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_block (one native block as raw native-endian bytes)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_block(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    block_x: usize,
+    block_y: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let bytes = read_block_bytes(&band, (block_x, block_y))?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+/// Reads one block of `band` as native-endian bytes. The buffer always has the
+/// full block size; GDAL pads edge blocks.
+fn read_block_bytes(band: &RasterBand, block: (usize, usize)) -> gdal::errors::Result<Vec<u8>> {
+    // Unlike windowed reads, GDAL will not convert: the type must match the band
+    match band.band_type() {
+        GdalDataType::UInt8 => block_as_bytes(band, block, u8::to_ne_bytes),
+        GdalDataType::Int16 => block_as_bytes(band, block, i16::to_ne_bytes),
+        GdalDataType::UInt16 => block_as_bytes(band, block, u16::to_ne_bytes),
+        GdalDataType::Int32 => block_as_bytes(band, block, i32::to_ne_bytes),
+        GdalDataType::UInt32 => block_as_bytes(band, block, u32::to_ne_bytes),
+        GdalDataType::Float32 => block_as_bytes(band, block, f32::to_ne_bytes),
+        GdalDataType::Float64 => block_as_bytes(band, block, f64::to_ne_bytes),
+        _ => Err(GdalError::BadArgument("unsupported data type".to_string())),
+    }
+}
+
+fn block_as_bytes<T: Copy + GdalType, const N: usize>(
+    band: &RasterBand,
+    block: (usize, usize),
+    to_bytes: fn(T) -> [u8; N],
+) -> gdal::errors::Result<Vec<u8>> {
+    let buf = band.read_block::<T>(block)?;
+    Ok(buf.data().iter().flat_map(|&v| to_bytes(v)).collect())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: stream_blocks — send every block of a band to a process
+// ---------------------------------------------------------------------------
+
+/// Reads the band's blocks row by row on a background thread and sends each
+/// to `pid` as `{:block, block_x, block_y, binary}`, then `:done`. Errors,
+/// including a bad band index, arrive as `{:error, {kind, message}}`.
+#[rustler::nif]
+fn gdal_stream_blocks(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    pid: LocalPid,
+) -> Atom {
+    std::thread::spawn(move || {
+        let mut env = OwnedEnv::new();
+        let result = stream_blocks(&resource, band_idx, |block_x, block_y, bytes| {
+            env.send_and_clear(&pid, |env| {
+                let mut binary = NewBinary::new(env, bytes.len());
+                binary.as_mut_slice().copy_from_slice(&bytes);
+                let binary: Binary = binary.into();
+                (atoms::block(), block_x, block_y, binary).encode(env)
+            })
+            .is_ok()
+        });
+        // A failed send means the receiver is gone; there is no one to tell.
+        let _ = match result {
+            Ok(()) => env.send_and_clear(&pid, |env| atoms::done().encode(env)),
+            Err(e) => env.send_and_clear(&pid, |env| (atoms::error(), e).encode(env)),
+        };
+    });
+    atoms::ok()
+}
+
+/// Calls `send` with each block in row-major order, locking the dataset
+/// only while a block is read so other calls can interleave. Stops early,
+/// successfully, once `send` returns `false`.
+fn stream_blocks(
+    resource: &DatasetResource,
+    band_idx: usize,
+    mut send: impl FnMut(usize, usize, Vec<u8>) -> bool,
+) -> Result<(), Error> {
+    let (blocks_x, blocks_y) = {
+        let ds = resource.lock()?;
+        let band = raster_band(&ds, band_idx)?;
+        let (width, height) = band.size();
+        let (block_w, block_h) = band.block_size();
+        (width.div_ceil(block_w), height.div_ceil(block_h))
+    };
+
+    for block_y in 0..blocks_y {
+        for block_x in 0..blocks_x {
+            let bytes = {
+                let ds = resource.lock()?;
+                let band = raster_band(&ds, band_idx)?;
+                read_block_bytes(&band, (block_x, block_y))?
+            };
+            if !send(block_x, block_y, bytes) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: overview_count
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_overview_count(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<usize, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let count = band.overview_count()?;
+    Ok(count.max(0) as usize)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: overview_sizes — {x, y} of every overview level, largest first
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_overview_sizes(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Vec<(usize, usize)>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let count = band.overview_count()?.max(0) as usize;
+    (0..count)
+        .map(|idx| Ok(band.overview(idx)?.size()))
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: build_overviews — generate pyramids for every band
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_build_overviews(
+    resource: ResourceArc<DatasetResource>,
+    resampling: Atom,
+    factors: Vec<i32>,
+) -> NifResult<Atom> {
+    unit_result(build_overviews(&resource, resampling, &factors))
+}
+
+fn build_overviews(
+    resource: &DatasetResource,
+    resampling: Atom,
+    factors: &[i32],
+) -> Result<(), Error> {
+    if factors.is_empty() {
+        return Err(Error::invalid_argument("overview factor list is empty"));
+    }
+    let resampling = match resampling {
+        a if a == atoms::nearest() => "NEAREST",
+        a if a == atoms::average() => "AVERAGE",
+        a if a == atoms::gauss() => "GAUSS",
+        a if a == atoms::cubic() => "CUBIC",
+        a if a == atoms::lanczos() => "LANCZOS",
+        _ => return Err(Error::invalid_argument("unsupported resampling algorithm")),
+    };
+    // A read-only dataset gets an external `.ovr`, which the reader
+    // handles, already open, would never see.
+    if !resource.readers.is_empty() {
+        return Err(Error::invalid_argument(
+            "cannot build overviews through a thread-safe dataset",
+        ));
+    }
+    let mut ds = resource.lock_primary()?;
+    ds.build_overviews(resampling, factors, &[])
+        .map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_overview (full overview level as raw native-endian bytes)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_overview(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    overview_idx: usize,
+) -> Result<Binary, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let count = band.overview_count()?.max(0) as usize;
+    if overview_idx >= count {
+        return Err(Error::invalid_argument(format!(
+            "overview index {overview_idx} out of range, band has {count} overviews"
+        )));
+    }
+    let overview = band.overview(overview_idx)?;
+    let size = overview.size();
+    let bytes = read_window_bytes(&overview, (0, 0), size)?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: read_for_resolution — a window at an output size, from the overview
+// closest to that resolution
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_for_resolution(
+    env: Env,
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    window: (isize, isize, usize, usize),
+    out_size: (usize, usize),
+    resampling: Atom,
+) -> Result<Binary, Error> {
+    let (x, y, w, h) = window;
+    let (out_w, out_h) = out_size;
+    let resample = atom_to_resample_alg(resampling)?;
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let (width, height) = band.size();
+    let inside = window_inside(window, (width, height));
+    if !inside || w == 0 || h == 0 || out_w == 0 || out_h == 0 {
+        return Err(Error::invalid_argument(format!(
+            "window {{{x}, {y}, {w}, {h}}} must be non-empty and inside the \
+             {width}x{height} band, and the output size non-empty"
+        )));
+    }
+
+    // The smallest overview still holding at least out_size pixels of the
+    // window; otherwise the full-resolution band.
+    let mut source: Option<(RasterBand, (usize, usize))> = None;
+    for idx in 0..band.overview_count()?.max(0) as usize {
+        let overview = band.overview(idx)?;
+        let (ovr_w, ovr_h) = overview.size();
+        // w * ovr_w / width overview pixels span the window horizontally;
+        // u128 as out_size is unbounded caller input
+        let covers = |len: usize, ovr: usize, out: usize, full: usize| {
+            len as u128 * ovr as u128 >= out as u128 * full as u128
+        };
+        let meets = covers(w, ovr_w, out_w, width) && covers(h, ovr_h, out_h, height);
+        let smaller = source.as_ref().is_none_or(|(_, best)| ovr_w < best.0);
+        if meets && smaller {
+            source = Some((overview, (ovr_w, ovr_h)));
+        }
+    }
+
+    let (read_band, read) = match &source {
+        Some((overview, (ovr_w, ovr_h))) => {
+            // Map the window onto the overview's coarser pixel grid
+            let map = |start: isize, len: usize, full: usize, ovr: usize| {
+                let scale = ovr as f64 / full as f64;
+                let start = ((start as f64 * scale).round() as usize).min(ovr - 1);
+                let len = ((len as f64 * scale).round() as usize).clamp(1, ovr - start);
+                (start, len)
+            };
+            let (ox, ow) = map(x, w, width, *ovr_w);
+            let (oy, oh) = map(y, h, height, *ovr_h);
+            (overview, ((ox as isize, oy as isize), (ow, oh)))
+        }
+        None => (&band, ((x, y), (w, h))),
+    };
+    let bytes = read_bytes(
+        read_band,
+        ReadSpec {
+            window: read.0,
+            window_size: read.1,
+            buffer_size: out_size,
+            resample: Some(resample),
+        },
+    )?;
+
+    let mut binary = NewBinary::new(env, bytes.len());
+    binary.as_mut_slice().copy_from_slice(&bytes);
+    Ok(binary.into())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: write_band (full band from raw native-endian bytes)
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_write_band(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    data: Binary,
+    data_type: Atom,
+) -> NifResult<Atom> {
+    unit_result(write_band(&resource, band_idx, data.as_slice(), data_type))
+}
+
+fn write_band(
+    resource: &DatasetResource,
+    band_idx: usize,
+    bytes: &[u8],
+    data_type: Atom,
+) -> Result<(), Error> {
+    let data_type = atom_to_data_type(data_type)?;
+    let ds = resource.lock_for_update()?;
+    let mut band = raster_band(&ds, band_idx)?;
+    let size = band.size();
+
+    let expected = size.0 * size.1 * data_type.bytes() as usize;
+    if bytes.len() != expected {
+        return Err(Error::invalid_argument(format!(
+            "binary is {} bytes, expected {expected} for a {}x{} band",
+            bytes.len(),
+            size.0,
+            size.1
+        )));
+    }
+
+    match data_type {
+        GdalDataType::UInt8 => write_from_bytes(&mut band, size, bytes, u8::from_ne_bytes),
+        GdalDataType::Int16 => write_from_bytes(&mut band, size, bytes, i16::from_ne_bytes),
+        GdalDataType::UInt16 => write_from_bytes(&mut band, size, bytes, u16::from_ne_bytes),
+        GdalDataType::Int32 => write_from_bytes(&mut band, size, bytes, i32::from_ne_bytes),
+        GdalDataType::UInt32 => write_from_bytes(&mut band, size, bytes, u32::from_ne_bytes),
+        GdalDataType::Float32 => write_from_bytes(&mut band, size, bytes, f32::from_ne_bytes),
+        _ => write_from_bytes(&mut band, size, bytes, f64::from_ne_bytes),
+    }
+    .map_err(Error::from)
+}
+
+fn write_from_bytes<T: Copy + GdalType, const N: usize>(
+    band: &mut RasterBand,
+    size: (usize, usize),
+    bytes: &[u8],
+    from_bytes: fn([u8; N]) -> T,
+) -> gdal::errors::Result<()> {
+    let data = bytes
+        .chunks_exact(N)
+        .map(|chunk| from_bytes(chunk.try_into().expect("chunks_exact yields N bytes")))
+        .collect();
+    band.write((0, 0), size, &mut Buffer::new(size, data))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: flush_cache — persist buffered writes without closing
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_flush_cache(resource: ResourceArc<DatasetResource>) -> NifResult<Atom> {
+    unit_result(flush_cache(&resource))
+}
+
+fn flush_cache(resource: &DatasetResource) -> Result<(), Error> {
+    let mut ds = resource.lock()?;
+    ds.flush_cache().map_err(Error::from)
+}
+
+/// Encodes a unit result as `:ok` or `{:error, {kind, message}}`.
+fn unit_result(result: Result<(), Error>) -> NifResult<Atom> {
+    result.map(|()| atoms::ok()).map_err(rustler::Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_type
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_type(resource: ResourceArc<DatasetResource>, band_idx: usize) -> Result<Atom, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let dt = band.band_type();
+    Ok(data_type_to_atom(dt))
+}
+
+fn data_type_to_atom(dt: GdalDataType) -> Atom {
+    match dt {
+        GdalDataType::UInt8 => atoms::uint8(),
+        GdalDataType::UInt16 => atoms::uint16(),
+        GdalDataType::Int16 => atoms::int16(),
+        GdalDataType::UInt32 => atoms::uint32(),
+        GdalDataType::Int32 => atoms::int32(),
+        GdalDataType::Float32 => atoms::float32(),
+        GdalDataType::Float64 => atoms::float64(),
+        _ => atoms::unknown(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_type_info — data type plus what is needed to decode its bytes
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct BandTypeInfo {
+    r#type: Atom,
+    size: u8,
+    signed: bool,
+    endianness: Atom,
+}
+
+#[rustler::nif]
+fn gdal_band_type_info(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<BandTypeInfo, Error> {
+    let ds = resource.lock()?;
+    let dt = read_type(raster_band(&ds, band_idx)?.band_type());
+    Ok(BandTypeInfo {
+        r#type: data_type_to_atom(dt),
+        size: dt.bytes(),
+        signed: dt.is_signed(),
+        endianness: native_endianness(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: data_type_size — bytes per element of a data type atom
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_data_type_size(data_type: Atom) -> Result<u8, Error> {
+    // GDALGetDataTypeSizeBytes
+    Ok(atom_to_data_type(data_type)?.bytes())
+}
+
+/// Inverse of `data_type_to_atom`, for the data types the NIFs can read and write.
+fn atom_to_data_type(atom: Atom) -> Result<GdalDataType, Error> {
+    let dt = match atom {
+        a if a == atoms::uint8() => GdalDataType::UInt8,
+        a if a == atoms::uint16() => GdalDataType::UInt16,
+        a if a == atoms::int16() => GdalDataType::Int16,
+        a if a == atoms::uint32() => GdalDataType::UInt32,
+        a if a == atoms::int32() => GdalDataType::Int32,
+        a if a == atoms::float32() => GdalDataType::Float32,
+        a if a == atoms::float64() => GdalDataType::Float64,
+        _ => return Err(Error::invalid_argument("unsupported data type")),
+    };
+    Ok(dt)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_color_interp / set_band_color_interp
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_color_interp(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Atom, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let interp = band.color_interpretation();
+    Ok(color_interps()
+        .into_iter()
+        .find(|(ci, _)| *ci == interp)
+        .map_or_else(atoms::undefined, |(_, atom)| atom))
+}
+
+#[rustler::nif]
+fn gdal_set_band_color_interp(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    interp: Atom,
+) -> NifResult<Atom> {
+    unit_result(set_band_color_interp(&resource, band_idx, interp))
+}
+
+fn set_band_color_interp(
+    resource: &DatasetResource,
+    band_idx: usize,
+    interp: Atom,
+) -> Result<(), Error> {
+    let interp = color_interps()
+        .into_iter()
+        .find(|(_, atom)| *atom == interp)
+        .map(|(ci, _)| ci)
+        .ok_or_else(|| Error::invalid_argument("unsupported color interpretation"))?;
+    let ds = resource.lock_for_update()?;
+    let mut band = raster_band(&ds, band_idx)?;
+    band.set_color_interpretation(interp).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: is_paletted — whether band 1 holds palette indices
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_is_paletted(resource: ResourceArc<DatasetResource>) -> Result<bool, Error> {
+    let ds = resource.lock()?;
+    if ds.raster_count() == 0 {
+        return Ok(false);
+    }
+    let interp = raster_band(&ds, 1)?.color_interpretation();
+    Ok(interp == ColorInterpretation::PaletteIndex)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: has_alpha — the first band interpreted as alpha, if any
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_has_alpha(resource: ResourceArc<DatasetResource>) -> Result<Option<usize>, Error> {
+    let ds = resource.lock()?;
+    for idx in 1..=ds.raster_count() {
+        if raster_band(&ds, idx)?.color_interpretation() == ColorInterpretation::AlphaBand {
+            return Ok(Some(idx));
+        }
+    }
+    Ok(None)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: color_table — palette entries as RGBA, nil if the band has none
+// ---------------------------------------------------------------------------
+type Rgba = (u8, u8, u8, u8);
+
+#[rustler::nif]
+fn gdal_color_table(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Option<Vec<Rgba>>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let Some(table) = band.color_table() else {
+        return Ok(None);
+    };
+
+    let channel = |v: i16| v.clamp(0, 255) as u8;
+    (0..table.entry_count())
+        .map(|i| {
+            table
+                .entry_as_rgb(i)
+                .map(|e| (channel(e.r), channel(e.g), channel(e.b), channel(e.a)))
+                .ok_or_else(|| {
+                    Error::new(
+                        atoms::gdal_error(),
+                        format!("color table entry {i} cannot be expressed as RGBA"),
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+fn color_interps() -> [(ColorInterpretation, Atom); 17] {
+    [
+        (ColorInterpretation::Undefined, atoms::undefined()),
+        (ColorInterpretation::GrayIndex, atoms::gray()),
+        (ColorInterpretation::PaletteIndex, atoms::palette()),
+        (ColorInterpretation::RedBand, atoms::red()),
+        (ColorInterpretation::GreenBand, atoms::green()),
+        (ColorInterpretation::BlueBand, atoms::blue()),
+        (ColorInterpretation::AlphaBand, atoms::alpha()),
+        (ColorInterpretation::HueBand, atoms::hue()),
+        (ColorInterpretation::SaturationBand, atoms::saturation()),
+        (ColorInterpretation::LightnessBand, atoms::lightness()),
+        (ColorInterpretation::CyanBand, atoms::cyan()),
+        (ColorInterpretation::MagentaBand, atoms::magenta()),
+        (ColorInterpretation::YellowBand, atoms::yellow()),
+        (ColorInterpretation::BlackBand, atoms::black()),
+        (ColorInterpretation::YCbCrSpaceYBand, atoms::ycbcr_y()),
+        (ColorInterpretation::YCbCrSpaceCbBand, atoms::ycbcr_cb()),
+        (ColorInterpretation::YCbCrSpaceCrBand, atoms::ycbcr_cr()),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// NIF: no_data_value
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_no_data_value(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Option<f64>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.no_data_value())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_no_data_value / delete_no_data_value
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_no_data_value(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    value: f64,
+) -> NifResult<Atom> {
+    unit_result(set_no_data_value(&resource, band_idx, Some(value)))
+}
+
+#[rustler::nif]
+fn gdal_delete_no_data_value(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> NifResult<Atom> {
+    unit_result(set_no_data_value(&resource, band_idx, None))
+}
+
+/// Sets the band's nodata value, or deletes it when `value` is `None`.
+fn set_no_data_value(
+    resource: &DatasetResource,
+    band_idx: usize,
+    value: Option<f64>,
+) -> Result<(), Error> {
+    let ds = resource.lock_for_update()?;
+    let mut band = raster_band(&ds, band_idx)?;
+    band.set_no_data_value(value).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_checksum — GDALChecksumImage over the band or a window of it
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_checksum(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    window: Option<(isize, isize, usize, usize)>,
+) -> Result<u16, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let (x, y, w, h) = window.unwrap_or_else(|| {
+        let (w, h) = band.size();
+        (0, 0, w, h)
+    });
+    band.checksum((x, y), (w, h)).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_statistics — min/max/mean/std_dev, approximate from overviews
+// when `approx` is true
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct BandStatistics {
+    min: f64,
+    max: f64,
+    mean: f64,
+    std_dev: f64,
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_statistics(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    approx: bool,
+) -> Result<BandStatistics, Error> {
+    band_statistics(&resource, band_idx, approx)?
+        .ok_or_else(|| Error::new(atoms::all_nodata(), "band has no valid pixels"))
+}
+
+/// Returns `None` when the band has no valid (non-nodata) pixels.
+///
+/// Computed here rather than with `GDALComputeRasterStatistics`, which
+/// stores the result as `STATISTICS_*` metadata: on a read-only dataset
+/// that means writing a `.aux.xml` next to the caller's file.
+fn band_statistics(
+    resource: &DatasetResource,
+    band_idx: usize,
+    approx: bool,
+) -> Result<Option<BandStatistics>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    if !approx {
+        return scan_statistics(&band);
+    }
+
+    // As GDAL's own approximate statistics: the smallest overview with at
+    // least this many pixels, or the band itself if it has none.
+    let sample = unsafe {
+        gdal_sys::GDALGetRasterSampleOverview(band.c_rasterband(), APPROX_STATISTICS_SAMPLES)
+    };
+    if sample.is_null() {
+        return Err(last_cpl_error("GDALGetRasterSampleOverview"));
+    }
+    // SAFETY: overviews belong to the band's dataset, locked above.
+    let sample = unsafe { RasterBand::from_c_rasterband(&ds, sample) };
+    scan_statistics(&sample)
+}
+
+/// GDAL's `GDALSTAT_APPROX_NUMSAMPLES`.
+const APPROX_STATISTICS_SAMPLES: c_int = 2500;
+
+/// Pixels `scan_statistics` reads at a time, bounding its memory use.
+const STATISTICS_STRIP_PIXELS: usize = 1 << 20;
+
+/// Reads `band` in strips of rows, skipping pixels its mask marks invalid
+/// (nodata, alpha, per-dataset masks) and NaNs. The standard deviation is
+/// the population one, as GDAL computes it.
+fn scan_statistics(band: &RasterBand) -> Result<Option<BandStatistics>, Error> {
+    let mask = band.open_mask_band()?;
+    let (width, height) = band.size();
+    let strip_rows = (STATISTICS_STRIP_PIXELS / width.max(1)).clamp(1, height.max(1));
+
+    let (mut count, mut mean, mut m2) = (0u64, 0.0f64, 0.0f64);
+    let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+    for row in (0..height).step_by(strip_rows) {
+        let rows = strip_rows.min(height - row);
+        let window = (0, row as isize);
+        let values = band.read_as::<f64>(window, (width, rows), (width, rows), None)?;
+        let valid = mask.read_as::<u8>(window, (width, rows), (width, rows), None)?;
+        for (&v, &m) in values.data().iter().zip(valid.data()) {
+            if m == 0 || v.is_nan() {
+                continue;
+            }
+            // Welford's update keeps the variance accurate over many pixels.
+            count += 1;
+            let delta = v - mean;
+            mean += delta / count as f64;
+            m2 += delta * (v - mean);
+            min = min.min(v);
+            max = max.max(v);
+        }
+    }
+
+    Ok((count > 0).then(|| BandStatistics {
+        min,
+        max,
+        mean,
+        std_dev: (m2 / count as f64).sqrt(),
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_min_max — data range only, cheaper than full statistics
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_min_max(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    approx: bool,
+) -> Result<(f64, f64), Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+
+    // Nodata pixels never count towards the range. Depending on the GDAL
+    // version an all-nodata band fails or yields a non-finite range, and
+    // the failure's message is not stable, so on either outcome count the
+    // valid pixels with the same scan band_statistics uses.
+    let result = band.compute_raster_min_max(approx);
+    if let Ok(min_max) = &result {
+        if min_max.min.is_finite() && min_max.max.is_finite() && min_max.min <= min_max.max {
+            return Ok((min_max.min, min_max.max));
+        }
+    }
+    match (scan_statistics(&band)?, result) {
+        (None, _) => Err(Error::new(atoms::all_nodata(), "band has no valid pixels")),
+        (Some(_), Err(e)) => Err(e.into()),
+        (Some(stats), Ok(_)) => Ok((stats.min, stats.max)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_histogram — bucket counts over [min, max]
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_histogram(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    min: f64,
+    max: f64,
+    buckets: usize,
+    include_out_of_range: bool,
+) -> Result<Vec<u64>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    let histogram = band.histogram(min, max, buckets, include_out_of_range, false)?;
+    Ok(histogram.counts().to_vec())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_scale / band_offset — physical value = raw * scale + offset
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_scale(resource: ResourceArc<DatasetResource>, band_idx: usize) -> Result<f64, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.scale().unwrap_or(1.0))
+}
+
+#[rustler::nif]
+fn gdal_band_offset(resource: ResourceArc<DatasetResource>, band_idx: usize) -> Result<f64, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.offset().unwrap_or(0.0))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_unit_type — e.g. "m" or "Kelvin"; empty when unspecified
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_unit_type(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.unit())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_category_names — class labels, indexed by pixel value
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_category_names(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Vec<String>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    // A list owned by the band, or null without categories
+    Ok(unsafe { string_list(gdal_sys::GDALGetRasterCategoryNames(band.c_rasterband())) })
+}
+
+/// Copies a null-terminated C string list; a null list is empty.
+///
+/// # Safety
+/// `list` must be null or a valid null-terminated `char **`.
+unsafe fn string_list(list: *const *mut c_char) -> Vec<String> {
+    let mut strings = Vec::new();
+    if !list.is_null() {
+        let mut entry = list;
+        while !(*entry).is_null() {
+            strings.push(CStr::from_ptr(*entry).to_string_lossy().into_owned());
+            entry = entry.add(1);
+        }
+    }
+    strings
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_rat — the default raster attribute table, if the band has one
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct RatColumn {
+    name: String,
+    r#type: Atom,
+    usage: Atom,
+}
+
+#[derive(NifMap)]
+struct AttributeTable {
+    columns: Vec<RatColumn>,
+    rows: Vec<Vec<FieldTerm>>,
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_rat(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<Option<AttributeTable>, Error> {
+    use gdal_sys::GDALRATFieldType as FieldType;
+
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    // The table is owned by the band and stays valid while it is borrowed
+    unsafe {
+        let rat = gdal_sys::GDALGetDefaultRAT(band.c_rasterband());
+        if rat.is_null() {
+            return Ok(None);
+        }
+
+        let column_count = gdal_sys::GDALRATGetColumnCount(rat);
+        let mut columns = Vec::with_capacity(column_count.max(0) as usize);
+        let mut types = Vec::with_capacity(columns.capacity());
+        for col in 0..column_count {
+            let field_type = gdal_sys::GDALRATGetTypeOfCol(rat, col);
+            types.push(field_type);
+            columns.push(RatColumn {
+                name: CStr::from_ptr(gdal_sys::GDALRATGetNameOfCol(rat, col))
+                    .to_string_lossy()
+                    .into_owned(),
+                r#type: match field_type {
+                    FieldType::GFT_Integer => atoms::integer(),
+                    FieldType::GFT_Real => atoms::real(),
+                    _ => atoms::string(),
+                },
+                usage: rat_usage_atom(gdal_sys::GDALRATGetUsageOfCol(rat, col)),
+            });
+        }
+
+        let rows = (0..gdal_sys::GDALRATGetRowCount(rat))
+            .map(|row| {
+                (0..column_count)
+                    .zip(&types)
+                    .map(|(col, &field_type)| match field_type {
+                        FieldType::GFT_Integer => {
+                            FieldTerm::Integer(gdal_sys::GDALRATGetValueAsInt(rat, row, col).into())
+                        }
+                        FieldType::GFT_Real => {
+                            FieldTerm::Real(gdal_sys::GDALRATGetValueAsDouble(rat, row, col))
+                        }
+                        _ => FieldTerm::String(
+                            CStr::from_ptr(gdal_sys::GDALRATGetValueAsString(rat, row, col))
+                                .to_string_lossy()
+                                .into_owned(),
+                        ),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Ok(Some(AttributeTable { columns, rows }))
+    }
+}
+
+fn rat_usage_atom(usage: gdal_sys::GDALRATFieldUsage::Type) -> Atom {
+    use gdal_sys::GDALRATFieldUsage as Usage;
+
+    match usage {
+        Usage::GFU_PixelCount => atoms::pixel_count(),
+        Usage::GFU_Name => atoms::name(),
+        Usage::GFU_Min => atoms::min(),
+        Usage::GFU_Max => atoms::max(),
+        Usage::GFU_MinMax => atoms::min_max(),
+        Usage::GFU_Red => atoms::red(),
+        Usage::GFU_Green => atoms::green(),
+        Usage::GFU_Blue => atoms::blue(),
+        Usage::GFU_Alpha => atoms::alpha(),
+        Usage::GFU_RedMin => atoms::red_min(),
+        Usage::GFU_GreenMin => atoms::green_min(),
+        Usage::GFU_BlueMin => atoms::blue_min(),
+        Usage::GFU_AlphaMin => atoms::alpha_min(),
+        Usage::GFU_RedMax => atoms::red_max(),
+        Usage::GFU_GreenMax => atoms::green_max(),
+        Usage::GFU_BlueMax => atoms::blue_max(),
+        Usage::GFU_AlphaMax => atoms::alpha_max(),
+        _ => atoms::generic(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: spatial_ref_wkt
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_spatial_ref_wkt(resource: ResourceArc<DatasetResource>) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let srs = ds.spatial_ref()?;
+    srs.to_wkt().map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: spatial_ref_proj4
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_spatial_ref_proj4(resource: ResourceArc<DatasetResource>) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let srs = ds.spatial_ref()?;
+    srs.to_proj4().map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: spatial_ref_epsg — EPSG code of the dataset SRS, if one matches
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_spatial_ref_epsg(resource: ResourceArc<DatasetResource>) -> Result<Option<i32>, Error> {
+    let ds = resource.lock()?;
+    let mut srs = ds.spatial_ref()?;
+    // Use the recorded authority if there is one, else let GDAL match it
+    // against well-known EPSG definitions.
+    if srs.auth_name().as_deref() != Some("EPSG") && srs.auto_identify_epsg().is_err() {
+        return Ok(None);
+    }
+    Ok(srs.auth_code().ok())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: gcp_count / gcps / gcp_spatial_ref_wkt — ground control points
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct GroundControlPoint {
+    id: String,
+    pixel: f64,
+    line: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+#[rustler::nif]
+fn gdal_gcp_count(resource: ResourceArc<DatasetResource>) -> Result<usize, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.gcps().len())
+}
+
+#[rustler::nif]
+fn gdal_gcps(resource: ResourceArc<DatasetResource>) -> Result<Vec<GroundControlPoint>, Error> {
+    let ds = resource.lock()?;
+    Ok(ds
+        .gcps()
+        .iter()
+        .map(|gcp| GroundControlPoint {
+            id: gcp.id(),
+            pixel: gcp.pixel(),
+            line: gcp.line(),
+            x: gcp.x(),
+            y: gcp.y(),
+            z: gcp.z(),
+        })
+        .collect())
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_gcp_spatial_ref_wkt(resource: ResourceArc<DatasetResource>) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let srs = ds.gcp_spatial_ref().ok_or_else(|| {
+        Error::new(
+            atoms::no_spatial_ref(),
+            "dataset has no GCP spatial reference",
+        )
+    })?;
+    srs.to_wkt().map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: transform_coords — reproject points between two spatial references
+// ---------------------------------------------------------------------------
+
+/// A spatial reference given from Elixir as an EPSG code or any definition
+/// GDAL accepts (WKT, PROJ string, `"EPSG:4326"`, ...).
+#[derive(NifUntaggedEnum)]
+enum SrsDefinition {
+    Epsg(u32),
+    Text(String),
+}
+
+impl SrsDefinition {
+    /// Builds the SRS with x/y in longitude/latitude (easting/northing) order
+    /// regardless of the authority's axis order.
+    fn to_spatial_ref(&self) -> Result<SpatialRef, Error> {
+        let mut srs = match self {
+            SrsDefinition::Epsg(code) => SpatialRef::from_epsg(*code)?,
+            SrsDefinition::Text(definition) => SpatialRef::from_definition(definition)?,
+        };
+        srs.set_axis_mapping_strategy(AxisMappingStrategy::TraditionalGisOrder);
+        Ok(srs)
+    }
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_transform_coords(
+    source: SrsDefinition,
+    target: SrsDefinition,
+    points: Vec<(f64, f64)>,
+) -> Result<Vec<(f64, f64)>, Error> {
+    let transform = CoordTransform::new(&source.to_spatial_ref()?, &target.to_spatial_ref()?)?;
+    transform_points(&transform, points)
+}
+
+fn transform_points(
+    transform: &CoordTransform,
+    points: Vec<(f64, f64)>,
+) -> Result<Vec<(f64, f64)>, Error> {
+    let (mut xs, mut ys): (Vec<f64>, Vec<f64>) = points.into_iter().unzip();
+    transform.transform_coords(&mut xs, &mut ys, &mut [])?;
+    Ok(xs.into_iter().zip(ys).collect())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: create_coord_transform / transform_with — reusable transforms
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_create_coord_transform(
+    source: SrsDefinition,
+    target: SrsDefinition,
+) -> Result<ResourceArc<CoordTransformResource>, Error> {
+    let transform = CoordTransform::new(&source.to_spatial_ref()?, &target.to_spatial_ref()?)?;
+    Ok(ResourceArc::new(CoordTransformResource {
+        inner: Mutex::new(SendCoordTransform(transform)),
+    }))
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_transform_with(
+    resource: ResourceArc<CoordTransformResource>,
+    points: Vec<(f64, f64)>,
+) -> Result<Vec<(f64, f64)>, Error> {
+    transform_points(&resource.lock().0, points)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_spatial_ref — assign an SRS given as an EPSG code or definition
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_spatial_ref(
+    resource: ResourceArc<DatasetResource>,
+    definition: SrsDefinition,
+) -> NifResult<Atom> {
+    unit_result(set_spatial_ref(&resource, &definition))
+}
+
+fn set_spatial_ref(resource: &DatasetResource, definition: &SrsDefinition) -> Result<(), Error> {
+    let srs = definition.to_spatial_ref()?;
+    let mut ds = resource.lock_for_update()?;
+    ds.set_spatial_ref(&srs).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: geo_transform
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_geo_transform(resource: ResourceArc<DatasetResource>) -> Result<Vec<f64>, Error> {
+    let ds = resource.lock()?;
+    let gt = ds.geo_transform()?;
+    Ok(gt.to_vec())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: geo_transform_map — the six coefficients under GDAL's names
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct GeoTransformMap {
+    origin_x: f64,
+    pixel_width: f64,
+    row_rotation: f64,
+    origin_y: f64,
+    col_rotation: f64,
+    pixel_height: f64,
+}
+
+#[rustler::nif]
+fn gdal_geo_transform_map(
+    resource: ResourceArc<DatasetResource>,
+) -> Result<GeoTransformMap, Error> {
+    let ds = resource.lock()?;
+    let [origin_x, pixel_width, row_rotation, origin_y, col_rotation, pixel_height] =
+        ds.geo_transform()?;
+    Ok(GeoTransformMap {
+        origin_x,
+        pixel_width,
+        row_rotation,
+        origin_y,
+        col_rotation,
+        pixel_height,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_geo_transform
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_geo_transform(
+    resource: ResourceArc<DatasetResource>,
+    coefficients: Vec<f64>,
+) -> NifResult<Atom> {
+    unit_result(set_geo_transform(&resource, &coefficients))
+}
+
+fn set_geo_transform(resource: &DatasetResource, coefficients: &[f64]) -> Result<(), Error> {
+    let gt: [f64; 6] = coefficients.try_into().map_err(|_| {
+        Error::invalid_argument(format!(
+            "geo-transform needs 6 coefficients, got {}",
+            coefficients.len()
+        ))
+    })?;
+    let mut ds = resource.lock_for_update()?;
+    ds.set_geo_transform(&gt).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: pixel_to_geo — apply the geo-transform to a pixel/line coordinate
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_pixel_to_geo(
+    resource: ResourceArc<DatasetResource>,
+    col: f64,
+    row: f64,
+) -> Result<(f64, f64), Error> {
+    let ds = resource.lock()?;
+    let gt = ds.geo_transform()?;
+    Ok(gt.apply(col, row))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: geo_to_pixel — apply the inverse geo-transform to a georeferenced point
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_geo_to_pixel(
+    resource: ResourceArc<DatasetResource>,
+    x: f64,
+    y: f64,
+) -> Result<(f64, f64), Error> {
+    let ds = resource.lock()?;
+    let inverse = ds.geo_transform()?.invert()?;
+    Ok(inverse.apply(x, y))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: metadata_item
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_metadata_item(
+    resource: ResourceArc<DatasetResource>,
+    key: String,
+    domain: String,
+) -> Result<Option<String>, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.metadata_item(&key, &domain))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: metadata_domains — list all metadata domain names
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_metadata_domains(resource: ResourceArc<DatasetResource>) -> Result<Vec<String>, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.metadata_domains())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: metadata_domain — all "Key=Value" entries for a domain
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_metadata_domain(
+    resource: ResourceArc<DatasetResource>,
+    domain: String,
+) -> Result<Option<Vec<String>>, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.metadata_domain(&domain))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: metadata — all entries for a domain as {key, value} pairs
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_metadata(
+    resource: ResourceArc<DatasetResource>,
+    domain: String,
+) -> Result<Vec<(String, String)>, Error> {
+    let ds = resource.lock()?;
+    Ok(metadata_pairs(&*ds, &domain))
+}
+
+/// Splits a domain's `"Key=Value"` entries; a missing domain has no entries.
+fn metadata_pairs(object: &impl Metadata, domain: &str) -> Vec<(String, String)> {
+    object
+        .metadata_domain(domain)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|entry| match entry.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (entry, String::new()),
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_metadata_item
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_metadata_item(
+    resource: ResourceArc<DatasetResource>,
+    key: String,
+    value: String,
+    domain: String,
+) -> NifResult<Atom> {
+    unit_result(set_metadata_item(&resource, &key, &value, &domain))
+}
+
+fn set_metadata_item(
+    resource: &DatasetResource,
+    key: &str,
+    value: &str,
+    domain: &str,
+) -> Result<(), Error> {
+    if key.is_empty() {
+        return Err(Error::invalid_argument("metadata key is empty"));
+    }
+    let mut ds = resource.lock_for_update()?;
+    ds.set_metadata_item(key, value, domain)
+        .map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_metadata_item
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_metadata_item(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    key: String,
+    domain: String,
+) -> Result<Option<String>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(band.metadata_item(&key, &domain))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_metadata — all entries of a band domain as {key, value} pairs
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_band_metadata(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    domain: String,
+) -> Result<Vec<(String, String)>, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    Ok(metadata_pairs(&band, &domain))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: subdatasets — {name, description} pairs from the SUBDATASETS domain
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_subdatasets(
+    resource: ResourceArc<DatasetResource>,
+) -> Result<Vec<(String, String)>, Error> {
+    let ds = resource.lock()?;
+    Ok(subdatasets(&ds))
+}
+
+/// Pairs each `SUBDATASET_<n>_NAME` entry with its `SUBDATASET_<n>_DESC`.
+fn subdatasets(ds: &Dataset) -> Vec<(String, String)> {
+    let items = metadata_pairs(ds, "SUBDATASETS");
+    items
+        .iter()
+        .filter_map(|(key, name)| {
+            let n = key.strip_prefix("SUBDATASET_")?.strip_suffix("_NAME")?;
+            let desc_key = format!("SUBDATASET_{n}_DESC");
+            let desc = items
+                .iter()
+                .find(|(k, _)| *k == desc_key)
+                .map(|(_, v)| v.clone())
+                .unwrap_or_default();
+            Some((name.clone(), desc))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: open_subdataset — open one of a dataset's subdatasets by name
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_open_subdataset(
+    resource: ResourceArc<DatasetResource>,
+    name: String,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let ds = resource.lock()?;
+    if !subdatasets(&ds).iter().any(|(n, _)| *n == name) {
+        return Err(Error::invalid_argument(format!(
+            "{name} is not a subdataset of this dataset"
+        )));
+    }
+    let sub = Dataset::open(&name)?;
+    Ok(ResourceArc::new(DatasetResource::new(sub)))
+}
+
+// ---------------------------------------------------------------------------
+// NIF: description — the dataset name GDAL assigned when opening
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_description(resource: ResourceArc<DatasetResource>) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    ds.description().map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: band_description — the description string for a band (1-based index)
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_band_description(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    let band = raster_band(&ds, band_idx)?;
+    band.description().map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: set_band_description
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_set_band_description(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    description: String,
+) -> NifResult<Atom> {
+    unit_result(set_band_description(&resource, band_idx, &description))
+}
+
+fn set_band_description(
+    resource: &DatasetResource,
+    band_idx: usize,
+    description: &str,
+) -> Result<(), Error> {
+    let ds = resource.lock_for_update()?;
+    let mut band = raster_band(&ds, band_idx)?;
+    band.set_description(description).map_err(Error::from)
+}
+
+// ---------------------------------------------------------------------------
+// NIF: driver_name
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_driver_name(resource: ResourceArc<DatasetResource>) -> Result<String, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.driver().short_name())
+}
+
+// ---------------------------------------------------------------------------
+// NIF: file_list — every file the dataset is made of, sidecars included
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_file_list(resource: ResourceArc<DatasetResource>) -> Result<Vec<String>, Error> {
+    let ds = resource.lock()?;
+    // The caller owns the list; drivers without files return null.
+    unsafe {
+        let list = gdal_sys::GDALGetFileList(ds.c_dataset());
+        let files = string_list(list);
+        gdal_sys::CSLDestroy(list);
+        Ok(files)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// NIF: info — gdalinfo-style summary in one call
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct BandInfo {
+    r#type: Atom,
+    no_data_value: Option<f64>,
+}
+
+#[derive(NifMap)]
+struct DatasetInfo {
+    raster_size: (usize, usize),
+    band_count: usize,
+    driver: String,
+    geo_transform: Option<Vec<f64>>,
+    spatial_ref_wkt: Option<String>,
+    bands: Vec<BandInfo>,
+}
+
+/// Bundles the properties `gdalinfo` reports so callers can inspect a dataset
+/// with a single lock acquisition. A missing geo-transform or SRS is `nil`
+/// rather than an error, since both are optional for a valid dataset.
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_info(resource: ResourceArc<DatasetResource>) -> Result<DatasetInfo, Error> {
+    let ds = resource.lock()?;
+    let bands = (1..=ds.raster_count())
+        .map(|idx| {
+            let band = raster_band(&ds, idx)?;
+            Ok(BandInfo {
+                r#type: data_type_to_atom(band.band_type()),
+                no_data_value: band.no_data_value(),
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(DatasetInfo {
+        raster_size: ds.raster_size(),
+        band_count: ds.raster_count(),
+        driver: ds.driver().short_name(),
+        geo_transform: ds.geo_transform().ok().map(|gt| gt.to_vec()),
+        spatial_ref_wkt: ds.spatial_ref().ok().and_then(|srs| srs.to_wkt().ok()),
+        bands,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// NIF: driver_list — registered drivers and whether they can write
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct DriverInfo {
+    short_name: String,
+    long_name: String,
+    can_create: bool,
+    can_create_copy: bool,
+}
+
+#[rustler::nif]
+fn gdal_driver_list() -> Vec<DriverInfo> {
+    DriverManager::all()
+        .map(|driver| DriverInfo {
+            short_name: driver.short_name(),
+            long_name: driver.long_name(),
+            can_create: has_capability(&driver, "DCAP_CREATE"),
+            can_create_copy: has_capability(&driver, "DCAP_CREATECOPY"),
+        })
+        .collect()
+}
+
+/// Looks up a driver by short name; GDAL reports an unknown name only as a
+/// null pointer, so name it in the error instead.
+fn driver_by_name(name: &str) -> Result<Driver, Error> {
+    DriverManager::get_driver_by_name(name)
+        .map_err(|_| Error::invalid_argument(format!("unknown driver {name}")))
+}
+
+/// Whether a driver advertises a `DCAP_*` capability.
+fn has_capability(driver: &Driver, capability: &str) -> bool {
+    driver.metadata_item(capability, "").as_deref() == Some("YES")
+}
+
+// ---------------------------------------------------------------------------
+// NIF: driver_metadata — file extensions and MIME type a driver produces
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct DriverMetadata {
+    extensions: Vec<String>,
+    mime_type: Option<String>,
+}
+
+#[rustler::nif]
+fn gdal_driver_metadata(driver_name: String) -> Result<DriverMetadata, Error> {
+    let driver = driver_by_name(&driver_name)?;
+    // DMD_EXTENSIONS lists every extension, space-separated; older drivers
+    // only set the single DMD_EXTENSION.
+    let extensions = driver
+        .metadata_item("DMD_EXTENSIONS", "")
+        .or_else(|| driver.metadata_item("DMD_EXTENSION", ""))
+        .map(|list| list.split_whitespace().map(String::from).collect())
+        .unwrap_or_default();
+    Ok(DriverMetadata {
+        extensions,
+        mime_type: driver.metadata_item("DMD_MIMETYPE", ""),
+    })
 }
 
 // ---------------------------------------------------------------------------
-// NIF: open
+// NIF: delete — remove a dataset and its sidecar files through its driver
 // ---------------------------------------------------------------------------
 #[rustler::nif(schedule = "DirtyIo")]
-fn gdal_open(path: String) -> Result<ResourceArc<DatasetResource>, String> {
-    let ds = Dataset::open(&path).map_err(gdal_err_to_string)?;
-    Ok(ResourceArc::new(DatasetResource {
-        inner: Mutex::new(ds),
-    }))
+fn gdal_delete(driver_name: String, path: String) -> NifResult<Atom> {
+    unit_result(driver_by_name(&driver_name).and_then(|driver| Ok(driver.delete(&path)?)))
 }
 
 // ---------------------------------------------------------------------------
-// NIF: raster_count
+// NIF: rename — move a dataset and its sidecar files through its driver
+// ---------------------------------------------------------------------------
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_rename(driver_name: String, new_path: String, old_path: String) -> NifResult<Atom> {
+    unit_result(
+        driver_by_name(&driver_name).and_then(|driver| Ok(driver.rename(&new_path, &old_path)?)),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// NIF: layer_count — number of vector layers in the dataset
 // ---------------------------------------------------------------------------
 #[rustler::nif]
-fn gdal_raster_count(resource: ResourceArc<DatasetResource>) -> Result<usize, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.raster_count())
+fn gdal_layer_count(resource: ResourceArc<DatasetResource>) -> Result<usize, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.layer_count())
 }
 
 // ---------------------------------------------------------------------------
-// NIF: raster_size
+// NIF: layer_names — vector layer names, in layer index order
 // ---------------------------------------------------------------------------
 #[rustler::nif]
-fn gdal_raster_size(resource: ResourceArc<DatasetResource>) -> Result<(usize, usize), String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.raster_size())
+fn gdal_layer_names(resource: ResourceArc<DatasetResource>) -> Result<Vec<String>, Error> {
+    let ds = resource.lock()?;
+    Ok(ds.layers().map(|layer| layer.name()).collect())
 }
 
 // ---------------------------------------------------------------------------
-// NIF: read_band (full band as raw u8 bytes)
+// NIF: layer_feature_count — number of features in a layer (0-based index)
 // ---------------------------------------------------------------------------
 #[rustler::nif(schedule = "DirtyIo")]
-fn gdal_read_band(
-    env: Env,
+fn gdal_layer_feature_count(
     resource: ResourceArc<DatasetResource>,
-    band_idx: usize,
-) -> Result<Binary, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let band = ds.rasterband(band_idx).map_err(gdal_err_to_string)?;
-    let band_type = band.band_type();
+    layer_idx: usize,
+) -> Result<u64, Error> {
+    let ds = resource.lock()?;
+    let layer = ds.layer(layer_idx)?;
+    Ok(layer.feature_count())
+}
 
-    // Read raw bytes regardless of data type
-    let bytes = match band_type {
-        GdalDataType::UInt8 => {
-            let buf = band.read_band_as::<u8>().map_err(gdal_err_to_string)?;
-            buf.data().to_vec()
-        }
-        GdalDataType::Int16 => {
-            let buf = band.read_band_as::<i16>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        GdalDataType::UInt16 => {
-            let buf = band.read_band_as::<u16>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        GdalDataType::Int32 => {
-            let buf = band.read_band_as::<i32>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        GdalDataType::UInt32 => {
-            let buf = band.read_band_as::<u32>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        GdalDataType::Float32 => {
-            let buf = band.read_band_as::<f32>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        GdalDataType::Float64 => {
-            let buf = band.read_band_as::<f64>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
-        }
-        _ => {
-            // Fallback: read as f64 and return raw bytes
-            let buf = band.read_band_as::<f64>().map_err(gdal_err_to_string)?;
-            buf.data()
-                .iter()
-                .flat_map(|v| v.to_ne_bytes())
-                .collect::<Vec<u8>>()
+// ---------------------------------------------------------------------------
+// NIF: read_features — up to `max_count` features as fid, WKT and fields
+// ---------------------------------------------------------------------------
+#[derive(NifMap)]
+struct FeatureRecord {
+    fid: Option<u64>,
+    geometry_wkt: Option<String>,
+    fields: HashMap<String, Option<FieldTerm>>,
+}
+
+/// A field value as the closest Elixir term; dates become ISO 8601 strings.
+#[derive(NifUntaggedEnum)]
+enum FieldTerm {
+    Integer(i64),
+    Real(f64),
+    String(String),
+    IntegerList(Vec<i64>),
+    RealList(Vec<f64>),
+    StringList(Vec<String>),
+}
+
+impl From<FieldValue> for FieldTerm {
+    fn from(value: FieldValue) -> Self {
+        match value {
+            FieldValue::IntegerValue(v) => FieldTerm::Integer(v.into()),
+            FieldValue::Integer64Value(v) => FieldTerm::Integer(v),
+            FieldValue::RealValue(v) => FieldTerm::Real(v),
+            FieldValue::StringValue(v) => FieldTerm::String(v),
+            FieldValue::IntegerListValue(v) => {
+                FieldTerm::IntegerList(v.into_iter().map(i64::from).collect())
+            }
+            FieldValue::Integer64ListValue(v) => FieldTerm::IntegerList(v),
+            FieldValue::RealListValue(v) => FieldTerm::RealList(v),
+            FieldValue::StringListValue(v) => FieldTerm::StringList(v),
+            FieldValue::DateValue(v) => FieldTerm::String(v.to_string()),
+            // chrono's Debug output is ISO 8601; its formatter needs a crate
+            // feature the gdal crate does not enable.
+            FieldValue::DateTimeValue(v) => FieldTerm::String(format!("{v:?}")),
         }
-    };
+    }
+}
 
-    let mut binary = NewBinary::new(env, bytes.len());
-    binary.as_mut_slice().copy_from_slice(&bytes);
-    Ok(binary.into())
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_read_features(
+    resource: ResourceArc<DatasetResource>,
+    layer_idx: usize,
+    max_count: usize,
+) -> Result<Vec<FeatureRecord>, Error> {
+    let ds = resource.lock()?;
+    let mut layer = ds.layer(layer_idx)?;
+    layer
+        .features()
+        .take(max_count)
+        .map(|feature| {
+            let geometry_wkt = feature.geometry().map(|g| g.wkt()).transpose()?;
+            Ok(FeatureRecord {
+                fid: feature.fid(),
+                geometry_wkt,
+                fields: feature
+                    .fields()
+                    .map(|(name, value)| (name, value.map(FieldTerm::from)))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// NIF: layer_geometry_type — geometry type of a layer's first geometry field
+// ---------------------------------------------------------------------------
+#[rustler::nif]
+fn gdal_layer_geometry_type(
+    resource: ResourceArc<DatasetResource>,
+    layer_idx: usize,
+) -> Result<Atom, Error> {
+    let ds = resource.lock()?;
+    let layer = ds.layer(layer_idx)?;
+    // Z and M variants report the same type as their 2D counterpart
+    let atom = match geometry_type_flatten(layer.defn().geometry_type()) {
+        OGRwkbGeometryType::wkbNone => atoms::none(),
+        OGRwkbGeometryType::wkbPoint => atoms::point(),
+        OGRwkbGeometryType::wkbLineString => atoms::line_string(),
+        OGRwkbGeometryType::wkbPolygon => atoms::polygon(),
+        OGRwkbGeometryType::wkbMultiPoint => atoms::multi_point(),
+        OGRwkbGeometryType::wkbMultiLineString => atoms::multi_line_string(),
+        OGRwkbGeometryType::wkbMultiPolygon => atoms::multi_polygon(),
+        OGRwkbGeometryType::wkbGeometryCollection => atoms::geometry_collection(),
+        _ => atoms::unknown(),
+    };
+    Ok(atom)
 }
 
 // ---------------------------------------------------------------------------
-// NIF: read_band_window (sub-region as raw u8 bytes)
+// NIF: layer_extent — {min_x, min_y, max_x, max_y}, scanning if needed
 // ---------------------------------------------------------------------------
 #[rustler::nif(schedule = "DirtyIo")]
-fn gdal_read_band_window(
-    env: Env,
+fn gdal_layer_extent(
     resource: ResourceArc<DatasetResource>,
-    band_idx: usize,
-    x: isize,
-    y: isize,
-    w: usize,
-    h: usize,
-) -> Result<Binary, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let band = ds.rasterband(band_idx).map_err(gdal_err_to_string)?;
-
-    // Always read as u8 for windowed reads — caller can cast based on band_type
-    let buf = band
-        .read_as::<u8>((x, y), (w, h), (w, h), None)
-        .map_err(gdal_err_to_string)?;
-    let data = buf.data();
-
-    let mut binary = NewBinary::new(env, data.len());
-    binary.as_mut_slice().copy_from_slice(data);
-    Ok(binary.into())
+    layer_idx: usize,
+) -> Result<(f64, f64, f64, f64), Error> {
+    let ds = resource.lock()?;
+    let layer = ds.layer(layer_idx)?;
+    let env = layer
+        .get_extent()
+        .map_err(|_| Error::new(atoms::gdal_error(), "layer has no geometries to bound"))?;
+    Ok((env.MinX, env.MinY, env.MaxX, env.MaxY))
 }
 
 // ---------------------------------------------------------------------------
-// NIF: band_type
+// NIF: rasterize_layer — burn a vector layer's geometries into a raster band
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_band_type(resource: ResourceArc<DatasetResource>, band_idx: usize) -> Result<Atom, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let band = ds.rasterband(band_idx).map_err(gdal_err_to_string)?;
-    let dt = band.band_type();
-    Ok(data_type_to_atom(dt))
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_rasterize_layer(
+    target: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    source: ResourceArc<DatasetResource>,
+    layer_idx: usize,
+    burn_value: f64,
+    attribute: Option<String>,
+) -> NifResult<Atom> {
+    unit_result(rasterize_layer(
+        &target,
+        band_idx,
+        &source,
+        layer_idx,
+        burn_value,
+        attribute.as_deref(),
+    ))
 }
 
-fn data_type_to_atom(dt: GdalDataType) -> Atom {
-    match dt {
-        GdalDataType::UInt8 => atoms::uint8(),
-        GdalDataType::UInt16 => atoms::uint16(),
-        GdalDataType::Int16 => atoms::int16(),
-        GdalDataType::UInt32 => atoms::uint32(),
-        GdalDataType::Int32 => atoms::int32(),
-        GdalDataType::Float32 => atoms::float32(),
-        GdalDataType::Float64 => atoms::float64(),
-        _ => atoms::unknown(),
+/// Burns each geometry with `burn_value`, or with its numeric `attribute`
+/// field when given; features with no geometry or a null attribute are
+/// skipped. Geometries must already be in the target's coordinate system.
+fn rasterize_layer(
+    target: &DatasetResource,
+    band_idx: usize,
+    source: &DatasetResource,
+    layer_idx: usize,
+    burn_value: f64,
+    attribute: Option<&str>,
+) -> Result<(), Error> {
+    // The source is unlocked before the target is locked, so the two may
+    // be one dataset, e.g. a GeoPackage holding both layer and raster.
+    let mut geometries = Vec::new();
+    let mut burn_values = Vec::new();
+    {
+        let src = source.lock()?;
+        let mut layer = src.layer(layer_idx)?;
+        let field_idx = attribute
+            .map(|name| layer.defn().field_index(name))
+            .transpose()?;
+        for feature in layer.features() {
+            let Some(geometry) = feature.geometry() else {
+                continue;
+            };
+            let value = match field_idx {
+                Some(idx) => match feature.field_as_double(idx)? {
+                    Some(v) => v,
+                    None => continue,
+                },
+                None => burn_value,
+            };
+            geometries.push(geometry.clone());
+            burn_values.push(value);
+        }
     }
+
+    let mut ds = target.lock_for_update()?;
+    check_band_index(band_idx, ds.raster_count())?;
+    gdal::raster::rasterize(&mut ds, &[band_idx], &geometries, &burn_values, None)?;
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// NIF: no_data_value
+// NIF: polygonize — one polygon feature per connected region of a band
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_no_data_value(
-    resource: ResourceArc<DatasetResource>,
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_polygonize(
+    source: ResourceArc<DatasetResource>,
     band_idx: usize,
-) -> Result<Option<f64>, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let band = ds.rasterband(band_idx).map_err(gdal_err_to_string)?;
-    Ok(band.no_data_value())
+    target: ResourceArc<DatasetResource>,
+    layer_name: String,
+    field_name: String,
+) -> Result<usize, Error> {
+    polygonize(&source, band_idx, &target, &layer_name, &field_name)
+}
+
+/// Creates `layer_name` in `target` with the source's SRS and a single
+/// `field_name` field holding each region's pixel value, then returns the new
+/// layer's index. Pixels masked out by the band's mask (e.g. nodata) are
+/// skipped. Float bands get a real field and `GDALFPolygonize`.
+fn polygonize(
+    source: &DatasetResource,
+    band_idx: usize,
+    target: &DatasetResource,
+    layer_name: &str,
+    field_name: &str,
+) -> Result<usize, Error> {
+    let (src, mut ds) = lock_source_target(source, target)?;
+    let band = raster_band(&src, band_idx)?;
+    let mask = band.open_mask_band()?;
+    let srs = src.spatial_ref().ok();
+    let float = matches!(
+        band.band_type(),
+        GdalDataType::Float32 | GdalDataType::Float64
+    );
+    let field_type = if float {
+        OGRFieldType::OFTReal
+    } else {
+        OGRFieldType::OFTInteger
+    };
+
+    let layer = ds.create_layer(LayerOptions {
+        name: layer_name,
+        srs: srs.as_ref(),
+        ty: OGRwkbGeometryType::wkbPolygon,
+        options: None,
+    })?;
+    layer.create_defn_fields(&[(field_name, field_type)])?;
+    let field_idx = layer.defn().field_index(field_name)? as c_int;
+
+    let polygonize_fn = if float {
+        gdal_sys::GDALFPolygonize
+    } else {
+        gdal_sys::GDALPolygonize
+    };
+    let rv = unsafe {
+        polygonize_fn(
+            band.c_rasterband(),
+            mask.c_rasterband(),
+            layer.c_layer(),
+            field_idx,
+            std::ptr::null_mut(),
+            None,
+            std::ptr::null_mut(),
+        )
+    };
+    if rv != gdal_sys::CPLErr::CE_None {
+        return Err(last_cpl_error("GDALPolygonize"));
+    }
+
+    Ok(ds.layer_count() - 1)
 }
 
 // ---------------------------------------------------------------------------
-// NIF: spatial_ref_wkt
+// NIF: fill_nodata — interpolate nodata gaps of a band in place
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_spatial_ref_wkt(resource: ResourceArc<DatasetResource>) -> Result<String, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let srs = ds.spatial_ref().map_err(gdal_err_to_string)?;
-    srs.to_wkt().map_err(gdal_err_to_string)
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_fill_nodata(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    max_search_distance: f64,
+    smoothing_iterations: u32,
+) -> NifResult<Atom> {
+    unit_result(fill_nodata(
+        &resource,
+        band_idx,
+        max_search_distance,
+        smoothing_iterations,
+    ))
+}
+
+/// Pixels excluded by the band's mask (e.g. nodata) are filled by inverse
+/// distance weighting from valid pixels within `max_search_distance` pixels,
+/// then smoothed with `smoothing_iterations` 3x3 passes.
+fn fill_nodata(
+    resource: &DatasetResource,
+    band_idx: usize,
+    max_search_distance: f64,
+    smoothing_iterations: u32,
+) -> Result<(), Error> {
+    if max_search_distance <= 0.0 {
+        return Err(Error::invalid_argument(
+            "max search distance must be positive",
+        ));
+    }
+    let ds = resource.lock_for_update()?;
+    let band = raster_band(&ds, band_idx)?;
+    let iterations = c_int::try_from(smoothing_iterations)
+        .map_err(|_| Error::invalid_argument("too many smoothing iterations"))?;
+
+    // A null mask band tells GDAL to use the target band's own mask.
+    let rv = unsafe {
+        gdal_sys::GDALFillNodata(
+            band.c_rasterband(),
+            std::ptr::null_mut(),
+            max_search_distance,
+            0,
+            iterations,
+            std::ptr::null_mut(),
+            None,
+            std::ptr::null_mut(),
+        )
+    };
+    if rv != gdal_sys::CPLErr::CE_None {
+        return Err(last_cpl_error("GDALFillNodata"));
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// NIF: spatial_ref_proj4
+// NIF: sieve_filter — merge small regions of a band into neighbours in place
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_spatial_ref_proj4(resource: ResourceArc<DatasetResource>) -> Result<String, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let srs = ds.spatial_ref().map_err(gdal_err_to_string)?;
-    srs.to_proj4().map_err(gdal_err_to_string)
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_sieve_filter(
+    resource: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    size_threshold: u32,
+    connectedness: u32,
+) -> NifResult<Atom> {
+    unit_result(sieve_filter(
+        &resource,
+        band_idx,
+        size_threshold,
+        connectedness,
+    ))
+}
+
+/// Regions smaller than `size_threshold` pixels take the value of their
+/// largest neighbouring region. Pixels excluded by the band's mask are
+/// neither merged nor used as neighbours.
+fn sieve_filter(
+    resource: &DatasetResource,
+    band_idx: usize,
+    size_threshold: u32,
+    connectedness: u32,
+) -> Result<(), Error> {
+    if connectedness != 4 && connectedness != 8 {
+        return Err(Error::invalid_argument(format!(
+            "connectedness must be 4 or 8, got {connectedness}"
+        )));
+    }
+    let threshold = c_int::try_from(size_threshold)
+        .map_err(|_| Error::invalid_argument("size threshold is too large"))?;
+    let ds = resource.lock_for_update()?;
+    let band = raster_band(&ds, band_idx)?;
+    let mask = band.open_mask_band()?;
+
+    let rv = unsafe {
+        gdal_sys::GDALSieveFilter(
+            band.c_rasterband(),
+            mask.c_rasterband(),
+            band.c_rasterband(),
+            threshold,
+            connectedness as c_int,
+            std::ptr::null_mut(),
+            None,
+            std::ptr::null_mut(),
+        )
+    };
+    if rv != gdal_sys::CPLErr::CE_None {
+        return Err(last_cpl_error("GDALSieveFilter"));
+    }
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
-// NIF: geo_transform
+// NIF: contour — elevation isolines of a band into a new line layer
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_geo_transform(resource: ResourceArc<DatasetResource>) -> Result<Vec<f64>, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let gt = ds.geo_transform().map_err(gdal_err_to_string)?;
-    Ok(gt.to_vec())
+
+/// Contour levels: every `interval` units from 0, or an explicit list.
+#[derive(NifUntaggedEnum)]
+enum ContourLevels {
+    Interval(f64),
+    Fixed(Vec<f64>),
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_contour(
+    source: ResourceArc<DatasetResource>,
+    band_idx: usize,
+    levels: ContourLevels,
+    target: ResourceArc<DatasetResource>,
+    layer_name: String,
+    field_name: String,
+) -> Result<usize, Error> {
+    contour(
+        &source,
+        band_idx,
+        &levels,
+        &target,
+        &layer_name,
+        &field_name,
+    )
+}
+
+/// Creates `layer_name` in `target` with the source's SRS and a real
+/// `field_name` field holding each line's level, then returns the new
+/// layer's index. The band's nodata value, if any, is excluded from the
+/// surface.
+fn contour(
+    source: &DatasetResource,
+    band_idx: usize,
+    levels: &ContourLevels,
+    target: &DatasetResource,
+    layer_name: &str,
+    field_name: &str,
+) -> Result<usize, Error> {
+    let mut options = CslStringList::new();
+    match levels {
+        ContourLevels::Interval(interval) if *interval > 0.0 => {
+            options.set_name_value("LEVEL_INTERVAL", &interval.to_string())?;
+        }
+        ContourLevels::Interval(_) => {
+            return Err(Error::invalid_argument("contour interval must be positive"));
+        }
+        ContourLevels::Fixed(fixed) if !fixed.is_empty() => {
+            let joined: Vec<String> = fixed.iter().map(|v| v.to_string()).collect();
+            options.set_name_value("FIXED_LEVELS", &joined.join(","))?;
+        }
+        ContourLevels::Fixed(_) => {
+            return Err(Error::invalid_argument("contour level list is empty"));
+        }
+    }
+
+    let (src, mut ds) = lock_source_target(source, target)?;
+    let band = raster_band(&src, band_idx)?;
+    if let Some(no_data) = band.no_data_value() {
+        options.set_name_value("NODATA", &no_data.to_string())?;
+    }
+    let srs = src.spatial_ref().ok();
+
+    let layer = ds.create_layer(LayerOptions {
+        name: layer_name,
+        srs: srs.as_ref(),
+        ty: OGRwkbGeometryType::wkbLineString,
+        options: None,
+    })?;
+    layer.create_defn_fields(&[(field_name, OGRFieldType::OFTReal)])?;
+    let field_idx = layer.defn().field_index(field_name)?;
+    options.set_name_value("ELEV_FIELD", &field_idx.to_string())?;
+
+    let rv = unsafe {
+        gdal_sys::GDALContourGenerateEx(
+            band.c_rasterband(),
+            layer.c_layer(),
+            options.as_ptr(),
+            None,
+            std::ptr::null_mut(),
+        )
+    };
+    if rv != gdal_sys::CPLErr::CE_None {
+        return Err(last_cpl_error("GDALContourGenerateEx"));
+    }
+
+    Ok(ds.layer_count() - 1)
+}
+
+/// In-place algorithms write through the band, which GDAL only reports as a
+/// generic write failure on a read-only dataset, as it does for a new layer;
+/// metadata, nodata and
+/// georeferencing would silently go to a `.aux.xml` sidecar instead of the
+/// file.
+fn ensure_writable(ds: &Dataset) -> Result<(), Error> {
+    let access = unsafe { gdal_sys::GDALGetAccess(ds.c_dataset()) };
+    if access as gdal_sys::GDALAccess::Type != gdal_sys::GDALAccess::GA_Update {
+        return Err(Error::invalid_argument(
+            "dataset is read-only, reopen it with access: :update",
+        ));
+    }
+    Ok(())
+}
+
+/// For direct `gdal_sys` calls, which report failure only through CPL.
+fn last_cpl_error(function: &str) -> Error {
+    Error::new(atoms::gdal_error(), cpl_error_message(function))
+}
+
+fn cpl_error_message(function: &str) -> String {
+    let message = unsafe { CStr::from_ptr(gdal_sys::CPLGetLastErrorMsg()) };
+    format!("{function} failed: {}", message.to_string_lossy())
+}
+
+/// Null-terminated argv for the gdal_utils `*OptionsNew` functions, which
+/// take `char **` but do not modify the strings.
+struct UtilityArgs {
+    _strings: Vec<CString>,
+    pointers: Vec<*mut c_char>,
+}
+
+impl UtilityArgs {
+    fn new(args: &[String]) -> Result<Self, Error> {
+        let strings = args
+            .iter()
+            .map(|arg| CString::new(arg.as_str()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::invalid_argument("option contains a NUL byte"))?;
+        let pointers = strings
+            .iter()
+            .map(|s| s.as_ptr() as *mut c_char)
+            .chain(std::iter::once(std::ptr::null_mut()))
+            .collect();
+        Ok(UtilityArgs {
+            _strings: strings,
+            pointers,
+        })
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut *mut c_char {
+        self.pointers.as_mut_ptr()
+    }
 }
 
 // ---------------------------------------------------------------------------
-// NIF: metadata_item
+// NIF: warp — reproject into a new dataset, as the gdalwarp utility does
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_metadata_item(
-    resource: ResourceArc<DatasetResource>,
-    key: String,
-    domain: String,
-) -> Result<Option<String>, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.metadata_item(&key, &domain))
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_warp(
+    source: ResourceArc<DatasetResource>,
+    srs: SrsDefinition,
+    resolution: Option<(f64, f64)>,
+    resampling: Atom,
+    output: Option<(String, String)>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let (driver, path) = output.unwrap_or_else(|| ("MEM".to_string(), String::new()));
+    let mut args = vec![
+        "-t_srs".to_string(),
+        srs.to_spatial_ref()?.to_wkt()?,
+        "-r".to_string(),
+        warp_resampling_name(resampling)?.to_string(),
+        "-of".to_string(),
+        driver,
+    ];
+    if let Some((x_res, y_res)) = resolution {
+        args.extend(["-tr".to_string(), x_res.to_string(), y_res.to_string()]);
+    }
+
+    let ds = warp(&source, &path, &args)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+/// gdalwarp's `-r` names; it has no equivalent of `:gauss`.
+fn warp_resampling_name(atom: Atom) -> Result<&'static str, Error> {
+    let name = match atom {
+        a if a == atoms::nearest() => "near",
+        a if a == atoms::bilinear() => "bilinear",
+        a if a == atoms::cubic() => "cubic",
+        a if a == atoms::lanczos() => "lanczos",
+        a if a == atoms::average() => "average",
+        _ => return Err(Error::invalid_argument("unsupported resampling algorithm")),
+    };
+    Ok(name)
+}
+
+/// Runs `GDALWarp` on a single source with gdalwarp command-line `args`,
+/// creating `dest` (empty for in-memory drivers).
+fn warp(source: &DatasetResource, dest: &str, args: &[String]) -> Result<Dataset, Error> {
+    let src = source.lock()?;
+    let mut argv = UtilityArgs::new(args)?;
+    let c_dest =
+        CString::new(dest).map_err(|_| Error::invalid_argument("path contains a NUL byte"))?;
+
+    unsafe {
+        let options = gdal_sys::GDALWarpAppOptionsNew(argv.as_mut_ptr(), std::ptr::null_mut());
+        if options.is_null() {
+            return Err(Error::invalid_argument(cpl_error_message(
+                "GDALWarpAppOptionsNew",
+            )));
+        }
+
+        let mut src_handle = src.c_dataset();
+        let mut usage_error: c_int = 0;
+        let out = gdal_sys::GDALWarp(
+            c_dest.as_ptr(),
+            std::ptr::null_mut(),
+            1,
+            &mut src_handle,
+            options,
+            &mut usage_error,
+        );
+        gdal_sys::GDALWarpAppOptionsFree(options);
+
+        if out.is_null() {
+            return Err(if usage_error != 0 {
+                Error::invalid_argument(cpl_error_message("GDALWarp"))
+            } else {
+                last_cpl_error("GDALWarp")
+            });
+        }
+        Ok(Dataset::from_c_dataset(out))
+    }
 }
 
 // ---------------------------------------------------------------------------
-// NIF: metadata_domains — list all metadata domain names
+// NIF: translate — convert and subset into a new dataset, as gdal_translate
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_metadata_domains(resource: ResourceArc<DatasetResource>) -> Result<Vec<String>, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.metadata_domains())
+
+/// `-scale` for gdal_translate: `true` maps the source min/max to 0..255,
+/// a tuple gives `{src_min, src_max, dst_min, dst_max}` explicitly.
+#[derive(NifUntaggedEnum)]
+enum ScaleSpec {
+    Range((f64, f64, f64, f64)),
+    Auto(bool),
+}
+
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_translate(
+    source: ResourceArc<DatasetResource>,
+    bands: Vec<usize>,
+    data_type: Option<Atom>,
+    scale: Option<ScaleSpec>,
+    window: Option<(isize, isize, usize, usize)>,
+    output: Option<(String, String)>,
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let (driver, path) = output.unwrap_or_else(|| ("MEM".to_string(), String::new()));
+    // gdal_translate reports bad bands only as a generic failure.
+    let band_count = source.lock()?.raster_count();
+    for &band_idx in &bands {
+        check_band_index(band_idx, band_count)?;
+    }
+
+    let mut args = vec!["-of".to_string(), driver];
+    for band_idx in bands {
+        args.extend(["-b".to_string(), band_idx.to_string()]);
+    }
+    if let Some(atom) = data_type {
+        args.extend(["-ot".to_string(), atom_to_data_type(atom)?.name()]);
+    }
+    match scale {
+        Some(ScaleSpec::Range((src_min, src_max, dst_min, dst_max))) => {
+            args.push("-scale".to_string());
+            args.extend([src_min, src_max, dst_min, dst_max].map(|v| v.to_string()));
+        }
+        Some(ScaleSpec::Auto(true)) => args.push("-scale".to_string()),
+        Some(ScaleSpec::Auto(false)) | None => {}
+    }
+    if let Some((x, y, w, h)) = window {
+        args.push("-srcwin".to_string());
+        args.extend([x.to_string(), y.to_string(), w.to_string(), h.to_string()]);
+    }
+
+    let ds = translate(&source, &path, &args)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
+}
+
+/// Runs `GDALTranslate` with gdal_translate command-line `args`, creating
+/// `dest` (empty for in-memory drivers).
+fn translate(source: &DatasetResource, dest: &str, args: &[String]) -> Result<Dataset, Error> {
+    let src = source.lock()?;
+    let mut argv = UtilityArgs::new(args)?;
+    let c_dest =
+        CString::new(dest).map_err(|_| Error::invalid_argument("path contains a NUL byte"))?;
+
+    unsafe {
+        let options = gdal_sys::GDALTranslateOptionsNew(argv.as_mut_ptr(), std::ptr::null_mut());
+        if options.is_null() {
+            return Err(Error::invalid_argument(cpl_error_message(
+                "GDALTranslateOptionsNew",
+            )));
+        }
+
+        let mut usage_error: c_int = 0;
+        let out =
+            gdal_sys::GDALTranslate(c_dest.as_ptr(), src.c_dataset(), options, &mut usage_error);
+        gdal_sys::GDALTranslateOptionsFree(options);
+
+        if out.is_null() {
+            return Err(if usage_error != 0 {
+                Error::invalid_argument(cpl_error_message("GDALTranslate"))
+            } else {
+                last_cpl_error("GDALTranslate")
+            });
+        }
+        Ok(Dataset::from_c_dataset(out))
+    }
 }
 
 // ---------------------------------------------------------------------------
-// NIF: metadata_domain — all "Key=Value" entries for a domain
+// NIF: encode_window — a window resampled and encoded as PNG or JPEG bytes
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_metadata_domain(
-    resource: ResourceArc<DatasetResource>,
-    domain: String,
-) -> Result<Option<Vec<String>>, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.metadata_domain(&domain))
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_encode_window(
+    env: Env,
+    source: ResourceArc<DatasetResource>,
+    window: (isize, isize, usize, usize),
+    out_size: (usize, usize),
+    format: Atom,
+    quality: Option<u32>,
+) -> Result<Binary, Error> {
+    let (driver, extension) = match format {
+        f if f == atoms::png() => ("PNG", "png"),
+        f if f == atoms::jpeg() => ("JPEG", "jpg"),
+        _ => return Err(Error::invalid_argument("format must be :png or :jpeg")),
+    };
+    let (x, y, w, h) = window;
+    let (out_w, out_h) = out_size;
+    if w == 0 || h == 0 || out_w == 0 || out_h == 0 {
+        return Err(Error::invalid_argument(
+            "window and output size must be non-empty",
+        ));
+    }
+
+    // PNG and JPEG only support CreateCopy, so gdal_translate subsets the
+    // window and the driver encodes it straight into /vsimem.
+    let mut args = vec!["-of".to_string(), driver.to_string(), "-srcwin".to_string()];
+    args.extend([x.to_string(), y.to_string(), w.to_string(), h.to_string()]);
+    args.extend(["-outsize".to_string(), out_w.to_string(), out_h.to_string()]);
+    match quality {
+        Some(q) if driver != "JPEG" => {
+            return Err(Error::invalid_argument(format!(
+                "quality {q} only applies to :jpeg"
+            )))
+        }
+        Some(q) if !(1..=100).contains(&q) => {
+            return Err(Error::invalid_argument(format!(
+                "quality must be between 1 and 100, got {q}"
+            )))
+        }
+        Some(q) => args.extend(["-co".to_string(), format!("QUALITY={q}")]),
+        None => {}
+    }
+
+    let id = VSIMEM_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir = format!("/vsimem/ex_gdal/{id}");
+    let path = format!("{dir}/window.{extension}");
+    // The file is only complete once the dataset is closed; the directory
+    // also catches sidecars such as .aux.xml.
+    let encoded = translate(&source, &path, &args).and_then(|ds| {
+        drop(ds);
+        Ok(gdal::vsi::call_on_mem_file_bytes(&path, |bytes| {
+            let mut binary = NewBinary::new(env, bytes.len());
+            binary.as_mut_slice().copy_from_slice(bytes);
+            Binary::from(binary)
+        })?)
+    });
+    let c_dir = CString::new(dir).expect("vsimem paths have no NUL byte");
+    unsafe { gdal_sys::VSIRmdirRecursive(c_dir.as_ptr()) };
+    encoded
 }
 
 // ---------------------------------------------------------------------------
-// NIF: band_description — the description string for a band (1-based index)
+// NIF: dem_processing — terrain derivatives of a DEM band, as gdaldem does
 // ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_band_description(
-    resource: ResourceArc<DatasetResource>,
+#[rustler::nif(schedule = "DirtyIo")]
+fn gdal_dem_processing(
+    source: ResourceArc<DatasetResource>,
     band_idx: usize,
-) -> Result<String, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    let band = ds.rasterband(band_idx).map_err(gdal_err_to_string)?;
-    band.description().map_err(gdal_err_to_string)
+    mode: Atom,
+    output: (String, String),
+    z_factor: Option<f64>,
+    scale: Option<f64>,
+    light: (Option<f64>, Option<f64>),
+) -> Result<ResourceArc<DatasetResource>, Error> {
+    let processing = dem_processing_name(mode)?;
+    let (driver, path) = output;
+    // gdaldem reports a bad -b only as a generic failure.
+    check_band_index(band_idx, source.lock()?.raster_count())?;
+    let (azimuth, altitude) = light;
+    if processing != "hillshade" && (azimuth.is_some() || altitude.is_some()) {
+        return Err(Error::invalid_argument(
+            "azimuth and altitude only apply to :hillshade",
+        ));
+    }
+
+    let mut args = vec![
+        "-of".to_string(),
+        driver,
+        "-b".to_string(),
+        band_idx.to_string(),
+    ];
+    for (flag, value) in [
+        ("-z", z_factor),
+        ("-s", scale),
+        ("-az", azimuth),
+        ("-alt", altitude),
+    ] {
+        if let Some(value) = value {
+            args.extend([flag.to_string(), value.to_string()]);
+        }
+    }
+
+    let ds = dem_processing(&source, &path, processing, &args)?;
+    Ok(ResourceArc::new(DatasetResource::new(ds)))
 }
 
-// ---------------------------------------------------------------------------
-// NIF: driver_name
-// ---------------------------------------------------------------------------
-#[rustler::nif]
-fn gdal_driver_name(resource: ResourceArc<DatasetResource>) -> Result<String, String> {
-    let ds = resource.inner.lock().map_err(|e| format!("{e}"))?;
-    Ok(ds.driver().short_name())
+/// gdaldem's processing names for the mode atoms; color-relief is not exposed.
+fn dem_processing_name(atom: Atom) -> Result<&'static str, Error> {
+    let name = match atom {
+        a if a == atoms::hillshade() => "hillshade",
+        a if a == atoms::slope() => "slope",
+        a if a == atoms::aspect() => "aspect",
+        a if a == atoms::roughness() => "roughness",
+        a if a == atoms::tri() => "TRI",
+        a if a == atoms::tpi() => "TPI",
+        _ => return Err(Error::invalid_argument("unsupported DEM processing mode")),
+    };
+    Ok(name)
+}
+
+/// Runs `GDALDEMProcessing` with gdaldem command-line `args`, creating `dest`.
+fn dem_processing(
+    source: &DatasetResource,
+    dest: &str,
+    processing: &str,
+    args: &[String],
+) -> Result<Dataset, Error> {
+    let src = source.lock()?;
+    let mut argv = UtilityArgs::new(args)?;
+    let c_dest =
+        CString::new(dest).map_err(|_| Error::invalid_argument("path contains a NUL byte"))?;
+    let c_processing = CString::new(processing).expect("processing names have no NUL byte");
+
+    unsafe {
+        let options =
+            gdal_sys::GDALDEMProcessingOptionsNew(argv.as_mut_ptr(), std::ptr::null_mut());
+        if options.is_null() {
+            return Err(Error::invalid_argument(cpl_error_message(
+                "GDALDEMProcessingOptionsNew",
+            )));
+        }
+
+        let mut usage_error: c_int = 0;
+        let out = gdal_sys::GDALDEMProcessing(
+            c_dest.as_ptr(),
+            src.c_dataset(),
+            c_processing.as_ptr(),
+            std::ptr::null(),
+            options,
+            &mut usage_error,
+        );
+        gdal_sys::GDALDEMProcessingOptionsFree(options);
+
+        if out.is_null() {
+            return Err(if usage_error != 0 {
+                Error::invalid_argument(cpl_error_message("GDALDEMProcessing"))
+            } else {
+                last_cpl_error("GDALDEMProcessing")
+            });
+        }
+        Ok(Dataset::from_c_dataset(out))
+    }
 }
 
 // ---------------------------------------------------------------------------
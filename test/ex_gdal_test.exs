@@ -3,6 +3,8 @@ defmodule ExGdalTest do
 
   @tinymarble "test/fixtures/tinymarble.tif"
   @dem_hills "test/fixtures/dem-hills.tiff"
+  @places "test/fixtures/places.geojson"
+  @gcp "test/fixtures/gcp.tif"
 
   describe "open/1" do
     test "opens a valid GeoTIFF" do
@@ -15,11 +17,267 @@ defmodule ExGdalTest do
     end
 
     test "returns error for missing file" do
-      assert {:error, reason} = ExGdal.open("nonexistent.tif")
+      assert {:error, {:open_failed, reason}} = ExGdal.open("nonexistent.tif")
       assert is_binary(reason)
     end
   end
 
+  describe "set_cache_max/1" do
+    test "bounds the cache that reads fill" do
+      assert :ok = ExGdal.set_cache_max(256 * 1024 * 1024)
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, _} = ExGdal.read_band(ds, 1)
+
+      assert {:ok, used} = ExGdal.get_cache_used()
+      assert used > 0 and used <= 256 * 1024 * 1024
+    end
+  end
+
+  describe "open_ex/2" do
+    test "opens with open options and an allowed driver" do
+      assert {:ok, %ExGdal.Dataset{driver: "GTiff", raster_count: 3}} =
+               ExGdal.open_ex(@tinymarble, open_options: ["NUM_THREADS=2"], drivers: ["GTiff"])
+    end
+
+    test "accepts open options as pairs" do
+      assert {:ok, _ds} = ExGdal.open_ex(@tinymarble, open_options: [num_threads: 2])
+    end
+
+    test "returns error when no allowed driver can open the file" do
+      assert {:error, {:open_failed, _}} = ExGdal.open_ex(@tinymarble, drivers: ["PNG"])
+    end
+
+    @tag :tmp_dir
+    test "opens an existing file for update", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "existing.tif")
+      {:ok, out} = ExGdal.create("GTiff", path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+
+      {:ok, read_only} = ExGdal.open(path)
+      assert {:error, _} = ExGdal.write_band(read_only, 1, :binary.copy(<<9>>, 16), :uint8)
+      :ok = ExGdal.close(read_only)
+
+      {:ok, ds} = ExGdal.open_ex(path, access: :update)
+      assert :ok = ExGdal.write_band(ds, 1, :binary.copy(<<9>>, 16), :uint8)
+      :ok = ExGdal.close(ds)
+
+      {:ok, ds} = ExGdal.open(path)
+      assert {:ok, <<9, _::binary>>} = ExGdal.read_band(ds, 1)
+    end
+
+    test "returns error for an unknown access mode" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.open_ex(@tinymarble, access: :append)
+    end
+  end
+
+  describe "set_config_option/2" do
+    test "sets, reads back and clears an option" do
+      key = "EX_GDAL_TEST_OPTION"
+      assert {:ok, nil} = ExGdal.get_config_option(key)
+      assert :ok = ExGdal.set_config_option(key, "YES")
+      assert {:ok, "YES"} = ExGdal.get_config_option(key)
+      assert :ok = ExGdal.set_config_option(key, nil)
+      assert {:ok, nil} = ExGdal.get_config_option(key)
+    end
+  end
+
+  describe "open_with_warnings/2" do
+    test "returns no warnings for a clean file" do
+      assert {:ok, %ExGdal.Dataset{driver: "GTiff"}, []} = ExGdal.open_with_warnings(@tinymarble)
+    end
+
+    test "returns warnings GDAL emitted while opening" do
+      assert {:ok, _ds, warnings} =
+               ExGdal.open_with_warnings(@tinymarble, open_options: ["NO_SUCH_OPTION=1"])
+
+      assert Enum.any?(warnings, &(&1 =~ "NO_SUCH_OPTION"))
+    end
+
+    test "returns errors as open_ex/2 does" do
+      assert {:error, {:open_failed, _}} = ExGdal.open_with_warnings("nonexistent.tif")
+    end
+  end
+
+  describe "open_memory/2" do
+    test "opens a GeoTIFF from bytes" do
+      bytes = File.read!(@tinymarble)
+      assert {:ok, ds} = ExGdal.open_memory(bytes, "tinymarble.tif")
+      assert ds.path == nil
+      assert ds.raster_count == 3
+      assert {:ok, from_memory} = ExGdal.read_band(ds, 1)
+
+      {:ok, on_disk} = ExGdal.open(@tinymarble)
+      assert {:ok, ^from_memory} = ExGdal.read_band(on_disk, 1)
+      assert :ok = ExGdal.close(ds)
+    end
+
+    test "returns error for bytes GDAL cannot read" do
+      assert {:error, {:open_failed, _}} = ExGdal.open_memory("not a raster", "junk.tif")
+    end
+  end
+
+  describe "open_vsicurl/2" do
+    test "returns error for an unreachable URL" do
+      options = %{"GDAL_HTTP_TIMEOUT" => "1"}
+
+      assert {:error, {:open_failed, _}} =
+               ExGdal.open_vsicurl("http://127.0.0.1:1/missing.tif", options)
+    end
+  end
+
+  describe "open_timeout/2" do
+    test "opens a local file like open/1" do
+      assert {:ok, ds} = ExGdal.open_timeout(@tinymarble, 5_000)
+      assert ds.raster_size == {100, 50}
+    end
+
+    test "expands relative local paths" do
+      relative = Path.relative_to_cwd(@tinymarble)
+      assert {:ok, ds} = ExGdal.open_timeout(relative, 5_000)
+      assert ds.path == Path.expand(relative)
+    end
+
+    test "reports a quick failure with its own kind" do
+      assert {:error, {:open_failed, _}} = ExGdal.open_timeout("nonexistent.tif", 60_000)
+    end
+
+    test "returns :timeout when the server never answers" do
+      # The kernel completes connections to a listening socket that nobody
+      # accepts, so curl's request is sent and then left unanswered.
+      {:ok, listener} = :gen_tcp.listen(0, [:binary, active: false])
+      {:ok, port} = :inet.port(listener)
+
+      assert {:error, {:timeout, message}} =
+               ExGdal.open_timeout("/vsicurl/http://127.0.0.1:#{port}/stalled.tif", 200)
+
+      assert message =~ ~r/timed out|timeout was reached/i
+    end
+
+    test "rejects a zero timeout" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.open_timeout(@tinymarble, 0)
+    end
+  end
+
+  describe "open_zip/2" do
+    @tag :tmp_dir
+    test "opens a raster inside an archive", %{tmp_dir: tmp_dir} do
+      archive = zip_fixture(tmp_dir)
+      assert {:ok, ds} = ExGdal.open_zip(archive, "rasters/marble.tif")
+      assert ds.raster_size == {100, 50}
+      assert "/vsizip/" <> _ = ds.path
+
+      {:ok, original} = ExGdal.open(@tinymarble)
+      assert ExGdal.read_band(ds, 1) == ExGdal.read_band(original, 1)
+    end
+
+    @tag :tmp_dir
+    test "returns error for a missing entry", %{tmp_dir: tmp_dir} do
+      archive = zip_fixture(tmp_dir)
+      assert {:error, {:open_failed, _}} = ExGdal.open_zip(archive, "missing.tif")
+    end
+  end
+
+  describe "open_threadsafe/1" do
+    test "serves concurrent reads with the same results" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, shared} = ExGdal.open_threadsafe(@dem_hills)
+      assert shared.raster_size == {333, 218}
+
+      rows = Enum.to_list(0..200//20)
+      expected = Enum.map(rows, &ExGdal.read_band_window(ds, 1, 0, &1, 333, 18))
+
+      results =
+        rows
+        |> Task.async_stream(&ExGdal.read_band_window(shared, 1, 0, &1, 333, 18))
+        |> Enum.map(fn {:ok, result} -> result end)
+
+      assert results == expected
+    end
+
+    test "rejects writes and metadata changes" do
+      {:ok, ds} = ExGdal.open_threadsafe(@tinymarble)
+      data = :binary.copy(<<9>>, 100 * 50)
+      assert {:error, {:invalid_argument, _}} = ExGdal.write_band(ds, 1, data, :uint8)
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_no_data_value(ds, 1, 0)
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_geo_transform(ds, [0, 1, 0, 0, 0, -1])
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_spatial_ref(ds, 4326)
+      assert {:error, {:invalid_argument, _}} = ExGdal.build_overviews(ds, :nearest, [2])
+
+      for _ <- 1..20 do
+        assert {:ok, nil} = ExGdal.no_data_value(ds, 1)
+      end
+    end
+
+    test "expands relative paths" do
+      {:ok, ds} = ExGdal.open_threadsafe(@tinymarble)
+      assert ds.path == Path.expand(@tinymarble)
+    end
+
+    test "closes every handle" do
+      {:ok, ds} = ExGdal.open_threadsafe(@tinymarble)
+      assert :ok = ExGdal.close(ds)
+
+      for _ <- 1..20 do
+        assert {:error, {:closed, _}} = ExGdal.read_band(ds, 1)
+      end
+    end
+
+    test "returns error for a missing file" do
+      assert {:error, {:open_failed, _}} = ExGdal.open_threadsafe("nonexistent.tif")
+    end
+  end
+
+  describe "list_zip/1" do
+    @tag :tmp_dir
+    test "lists raster entries only", %{tmp_dir: tmp_dir} do
+      assert {:ok, ["rasters/marble.tif"]} = ExGdal.list_zip(zip_fixture(tmp_dir))
+    end
+
+    test "returns error for a missing archive" do
+      assert {:error, {:open_failed, _}} = ExGdal.list_zip("nonexistent.zip")
+    end
+  end
+
+  describe "create/6" do
+    @tag :tmp_dir
+    test "creates a GeoTIFF with the requested layout", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "created.tif")
+      assert {:ok, ds} = ExGdal.create("GTiff", path, 16, 8, 2, :float32)
+      assert ds.driver == "GTiff"
+      assert ds.raster_count == 2
+      assert ds.raster_size == {16, 8}
+      assert {:ok, :float32} = ExGdal.band_type(ds, 2)
+    end
+
+    @tag :tmp_dir
+    test "returns error for a driver without create support", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "created.png")
+      assert {:error, {:invalid_argument, reason}} = ExGdal.create("PNG", path, 16, 8, 1, :uint8)
+      assert reason =~ "does not support creation"
+    end
+
+    @tag :tmp_dir
+    test "returns error for an unsupported data type", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "created.tif")
+      assert {:error, {:invalid_argument, _}} = ExGdal.create("GTiff", path, 16, 8, 1, :complex64)
+    end
+  end
+
+  describe "close/1" do
+    test "releases the dataset and rejects later calls" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert :ok = ExGdal.close(ds)
+      assert {:error, {:closed, _}} = ExGdal.read_band(ds, 1)
+      assert {:error, {:closed, _}} = ExGdal.band_type(ds, 1)
+    end
+
+    test "is idempotent" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert :ok = ExGdal.close(ds)
+      assert :ok = ExGdal.close(ds)
+    end
+  end
+
   describe "band_count/1" do
     test "tinymarble has 3 bands" do
       {:ok, ds} = ExGdal.open(@tinymarble)
@@ -27,6 +285,13 @@ defmodule ExGdalTest do
     end
   end
 
+  describe "dimensions/1" do
+    test "labels width and height" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, %{width: 333, height: 218}} = ExGdal.dimensions(ds)
+    end
+  end
+
   describe "raster_size/1" do
     test "returns width and height" do
       {:ok, ds} = ExGdal.open(@tinymarble)
@@ -36,6 +301,18 @@ defmodule ExGdalTest do
     end
   end
 
+  describe "band_size/2" do
+    test "returns the band width and height" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, {100, 50}} = ExGdal.band_size(ds, 1)
+    end
+
+    test "returns error for invalid band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 99, _}} = ExGdal.band_size(ds, 99)
+    end
+  end
+
   describe "read_band/2" do
     test "reads band 1 of tinymarble as binary" do
       {:ok, ds} = ExGdal.open(@tinymarble)
@@ -48,82 +325,1985 @@ defmodule ExGdalTest do
 
     test "returns error for invalid band index" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:error, _} = ExGdal.read_band(ds, 0)
+      assert {:error, {:invalid_band, 0, 3}} = ExGdal.read_band(ds, 0)
+    end
+
+    test "names the index and band count in the error" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 4, 3}} = ExGdal.read_band(ds, 4)
+      assert {:error, {:invalid_band, 4, 3}} = ExGdal.band_type(ds, 4)
     end
   end
 
-  describe "read_band_window/6" do
-    test "reads a sub-region" do
+  describe "read_band_resampled/5" do
+    test "reads a decimated band" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, data} = ExGdal.read_band_resampled(ds, 1, 33, 21, :average)
+      assert byte_size(data) == 33 * 21 * 4
+    end
+
+    test "matches read_band at full size with nearest" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:ok, data} = ExGdal.read_band_window(ds, 1, 0, 0, 10, 10)
-      assert byte_size(data) == 10 * 10
+      {:ok, full} = ExGdal.read_band(ds, 2)
+      assert {:ok, ^full} = ExGdal.read_band_resampled(ds, 2, 100, 50, :nearest)
+    end
+
+    test "returns error for an unknown algorithm" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_band_resampled(ds, 1, 10, 5, :sinc)
     end
   end
 
-  describe "band_type/2" do
-    test "tinymarble bands are uint8" do
+  describe "read_band_typed/2" do
+    test "returns data with its Nx type and shape" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, endianness} = ExGdal.native_endianness()
+
+      assert {:ok, %{data: data, type: :f32, shape: {218, 333}, endianness: ^endianness}} =
+               ExGdal.read_band_typed(ds, 1)
+
+      assert {:ok, ^data} = ExGdal.read_band(ds, 1)
+    end
+
+    test "maps uint8 bands to :u8" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:ok, :uint8} = ExGdal.band_type(ds, 1)
+      assert {:ok, %{type: :u8, shape: {50, 100}}} = ExGdal.read_band_typed(ds, 1)
     end
+  end
 
-    test "dem-hills band is float32" do
+  describe "read_band_rows/2" do
+    test "returns integer rows matching the raw band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, rows} = ExGdal.read_band_rows(ds, 1)
+      assert length(rows) == 50
+      assert Enum.all?(rows, &(length(&1) == 100))
+
+      {:ok, data} = ExGdal.read_band(ds, 1)
+      assert List.flatten(rows) == :binary.bin_to_list(data)
+    end
+
+    test "returns floats for float bands" do
       {:ok, ds} = ExGdal.open(@dem_hills)
-      assert {:ok, :float32} = ExGdal.band_type(ds, 1)
+      assert {:ok, [first_row | _] = rows} = ExGdal.read_band_rows(ds, 1)
+      assert length(rows) == 218
+      assert length(first_row) == 333
+
+      {:ok, window} = ExGdal.read_band_window(ds, 1, 0, 0, 333, 1)
+      assert first_row == for(<<v::float-32-native <- window>>, do: v)
+    end
+
+    @tag :tmp_dir
+    test "refuses bands above the size limit", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "big.tif"), 1025, 1024, 1, :uint8)
+      assert {:error, {:invalid_argument, reason}} = ExGdal.read_band_rows(ds, 1)
+      assert reason =~ "limited"
     end
   end
 
-  describe "no_data_value/2" do
-    test "tinymarble has no nodata" do
+  describe "read_band_list/2" do
+    @tag :tmp_dir
+    test "returns integers with the band type", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "ints.tif"), 3, 1, 1, :int16)
+      data = for v <- [-5, 0, 300], into: <<>>, do: <<v::signed-16-native>>
+      :ok = ExGdal.write_band(ds, 1, data, :int16)
+
+      assert {:ok, {:int16, [-5, 0, 300]}} = ExGdal.read_band_list(ds, 1)
+    end
+
+    test "returns floats for float bands" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, rows} = ExGdal.read_band_rows(ds, 1)
+      assert {:ok, {:float32, values}} = ExGdal.read_band_list(ds, 1)
+      assert values == List.flatten(rows)
+    end
+
+    @tag :tmp_dir
+    test "refuses bands above the size limit", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "big.tif"), 1025, 1024, 1, :uint8)
+      assert {:error, {:invalid_argument, reason}} = ExGdal.read_band_list(ds, 1)
+      assert reason =~ "limited"
+    end
+  end
+
+  describe "read_pixel/4" do
+    test "returns the value at a pixel" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:ok, nil} = ExGdal.no_data_value(ds, 1)
+      {:ok, <<raw>>} = ExGdal.read_band_window(ds, 2, 7, 3, 1, 1)
+      assert {:ok, value} = ExGdal.read_pixel(ds, 2, 7, 3)
+      assert value == raw / 1
+    end
+
+    @tag :tmp_dir
+    test "returns nil for nodata", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 2, 1, 1, :int16)
+      :ok = ExGdal.write_band(ds, 1, <<-9999::signed-16-native, 42::signed-16-native>>, :int16)
+      :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+
+      assert {:ok, nil} = ExGdal.read_pixel(ds, 1, 0, 0)
+      assert {:ok, 42.0} = ExGdal.read_pixel(ds, 1, 1, 0)
+    end
+
+    test "returns error outside the band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_pixel(ds, 1, 100, 0)
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_pixel(ds, 1, 0, -1)
     end
   end
 
-  describe "spatial_ref_wkt/1" do
-    test "returns WKT string for dataset with SRS" do
+  describe "sample_points/3" do
+    test "matches read_pixel/4 and yields nil outside the band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, a} = ExGdal.read_pixel(ds, 1, 0, 0)
+      {:ok, b} = ExGdal.read_pixel(ds, 1, 99, 49)
+
+      assert {:ok, [^a, ^b, nil, nil]} =
+               ExGdal.sample_points(ds, 1, [{0, 0}, {99, 49}, {100, 0}, {-1, 5}])
+    end
+
+    test "returns error for invalid band index" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 4, _}} = ExGdal.sample_points(ds, 4, [{0, 0}])
+    end
+  end
+
+  describe "sample_coords/4" do
+    test "samples the pixel containing each point" do
       {:ok, ds} = ExGdal.open(@dem_hills)
-      assert {:ok, wkt} = ExGdal.spatial_ref_wkt(ds)
-      assert is_binary(wkt)
-      assert String.contains?(wkt, "GEOGCS") or String.contains?(wkt, "GEOGCRS")
+      {:ok, {x, y}} = ExGdal.pixel_to_geo(ds, 120.5, 80.5)
+      {:ok, expected} = ExGdal.read_pixel(ds, 1, 120, 80)
+
+      assert {:ok, [^expected, nil]} = ExGdal.sample_coords(ds, 1, [{x, y}, {0, 0}])
     end
 
-    test "returns error for dataset without SRS" do
+    @tag :tmp_dir
+    test "interpolates bilinearly between pixel centres", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "ramp.tif"), 2, 2, 1, :float32)
+      :ok = ExGdal.set_geo_transform(ds, [0, 1, 0, 0, 0, 1])
+      data = for v <- [0.0, 10.0, 20.0, 30.0], into: <<>>, do: <<v::float-32-native>>
+      :ok = ExGdal.write_band(ds, 1, data, :float32)
+
+      assert {:ok, [15.0, 0.0, 5.0]} =
+               ExGdal.sample_coords(ds, 1, [{1.0, 1.0}, {0.2, 0.2}, {1.0, 0.5}],
+                 interpolation: :bilinear
+               )
+
+      assert {:ok, [0.0]} = ExGdal.sample_coords(ds, 1, [{0.9, 0.9}])
+    end
+
+    @tag :tmp_dir
+    test "yields nil when a neighbour is nodata", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "hole.tif"), 2, 1, 1, :float32)
+      :ok = ExGdal.set_geo_transform(ds, [0, 1, 0, 0, 0, 1])
+      :ok = ExGdal.write_band(ds, 1, <<1.0::float-32-native, -1.0::float-32-native>>, :float32)
+      :ok = ExGdal.set_no_data_value(ds, 1, -1)
+
+      assert {:ok, [nil]} = ExGdal.sample_coords(ds, 1, [{1.0, 0.5}], interpolation: :bilinear)
+      assert {:ok, [1.0]} = ExGdal.sample_coords(ds, 1, [{0.2, 0.5}], interpolation: :bilinear)
+    end
+
+    test "returns error for an unknown interpolation" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.sample_coords(ds, 1, [{0, 0}], interpolation: :cubic)
+    end
+  end
+
+  describe "read_mask_band/2" do
+    test "is all valid for a band without nodata" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:error, _} = ExGdal.spatial_ref_wkt(ds)
+      assert {:ok, mask} = ExGdal.read_mask_band(ds, 1)
+      assert mask == :binary.copy(<<255>>, 100 * 50)
+    end
+
+    test "marks nodata pixels invalid" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, mask} = ExGdal.read_mask_band(ds, 1)
+      assert byte_size(mask) == 333 * 218
+      assert mask |> :binary.bin_to_list() |> Enum.uniq() |> Enum.all?(&(&1 in [0, 255]))
     end
   end
 
-  describe "spatial_ref_proj4/1" do
-    test "returns proj4 string for dataset with SRS" do
+  describe "mask_flags/2" do
+    test "reports all-valid without nodata" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, %{all_valid: true, nodata: false}} = ExGdal.mask_flags(ds, 1)
+    end
+
+    test "reports a nodata-derived mask" do
       {:ok, ds} = ExGdal.open(@dem_hills)
-      assert {:ok, proj4} = ExGdal.spatial_ref_proj4(ds)
-      assert is_binary(proj4)
-      assert String.contains?(proj4, "+proj")
+      assert {:ok, %{all_valid: false, nodata: true}} = ExGdal.mask_flags(ds, 1)
     end
   end
 
-  describe "geo_transform/1" do
-    test "returns GeoTransform struct" do
+  describe "read_bands/2" do
+    test "interleaves RGB bands by pixel" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {w, h} = ds.raster_size
+      {:ok, r} = ExGdal.read_band(ds, 1)
+      {:ok, g} = ExGdal.read_band(ds, 2)
+      {:ok, b} = ExGdal.read_band(ds, 3)
+
+      assert {:ok, {rgb, count}} = ExGdal.read_bands(ds, [1, 2, 3])
+      assert count == w * h * 3
+      assert byte_size(rgb) == count
+
+      for i <- [0, 1, w * h - 1] do
+        assert binary_part(rgb, i * 3, 3) ==
+                 binary_part(r, i, 1) <> binary_part(g, i, 1) <> binary_part(b, i, 1)
+      end
+    end
+
+    test "returns error for an empty band list" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, _} = ExGdal.read_bands(ds, [])
+    end
+  end
+
+  describe "read_rgba/2" do
+    test "packs the color bands with an opaque alpha" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, {rgb, _}} = ExGdal.read_bands(ds, [1, 2, 3])
+
+      assert {:ok, rgba} = ExGdal.read_rgba(ds)
+      assert byte_size(rgba) == 100 * 50 * 4
+
+      for i <- [0, 1, 100 * 50 - 1] do
+        assert binary_part(rgba, i * 4, 4) == binary_part(rgb, i * 3, 3) <> <<255>>
+      end
+    end
+
+    test "reads a window" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, {rgb, _}} = ExGdal.read_bands(ds, [1, 2, 3])
+
+      assert {:ok, <<pixel::binary-size(4), _::binary>> = rgba} =
+               ExGdal.read_rgba(ds, {10, 5, 4, 2})
+
+      assert byte_size(rgba) == 4 * 2 * 4
+      assert pixel == binary_part(rgb, (5 * 100 + 10) * 3, 3) <> <<255>>
+    end
+
+    @tag :tmp_dir
+    test "takes alpha from the nodata mask", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "rgb.tif"), 2, 1, 3, :uint8)
+
+      for band <- 1..3 do
+        :ok = ExGdal.write_band(ds, band, <<0, 9>>, :uint8)
+        :ok = ExGdal.set_no_data_value(ds, band, 0)
+      end
+
+      assert {:ok, <<0, 0, 0, 0, 9, 9, 9, 255>>} = ExGdal.read_rgba(ds)
+    end
+
+    test "returns error for fewer than three bands" do
       {:ok, ds} = ExGdal.open(@dem_hills)
-      assert {:ok, %ExGdal.GeoTransform{} = gt} = ExGdal.geo_transform(ds)
-      assert is_float(gt.origin_x)
-      assert is_float(gt.origin_y)
-      assert is_float(gt.pixel_width)
-      assert is_float(gt.pixel_height)
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_rgba(ds)
     end
   end
 
-  describe "driver_name/1" do
-    test "returns GTiff for GeoTIFF" do
+  describe "read_band_le/2" do
+    test "returns little-endian pixels matching the native read" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, native} = ExGdal.read_band(ds, 1)
+      assert {:ok, le} = ExGdal.read_band_le(ds, 1)
+      assert byte_size(le) == byte_size(native)
+
+      assert for(<<v::float-32-little <- le>>, do: v) ==
+               for(<<v::float-32-native <- native>>, do: v)
+    end
+  end
+
+  describe "read_band_as_float/2" do
+    test "converts any band type to float32" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:ok, "GTiff"} = ExGdal.driver_name(ds)
+      {:ok, bytes} = ExGdal.read_band(ds, 1)
+      assert {:ok, floats} = ExGdal.read_band_as_float(ds, 1)
+      assert byte_size(floats) == 4 * byte_size(bytes)
+      assert for(<<v::float-32-little <- floats>>, do: trunc(v)) == :binary.bin_to_list(bytes)
+    end
+
+    @tag :tmp_dir
+    test "replaces nodata with NaN", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 2, 1, 1, :int16)
+      :ok = ExGdal.write_band(ds, 1, <<-9999::signed-16-native, 42::signed-16-native>>, :int16)
+      :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+
+      # NaN cannot be matched as a float, so compare its bit pattern
+      assert {:ok, <<0, 0, 192, 127, 42.0::float-32-little>>} = ExGdal.read_band_as_float(ds, 1)
     end
   end
 
-  describe "metadata_item/3" do
-    test "returns nil for missing key" do
+  describe "read_band_masked/2" do
+    @tag :tmp_dir
+    test "returns data and a validity mask", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 2, 1, 1, :int16)
+      :ok = ExGdal.write_band(ds, 1, <<-9999::signed-16-native, 42::signed-16-native>>, :int16)
+      :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+
+      assert {:ok, %{data: data, mask: <<0, 255>>}} = ExGdal.read_band_masked(ds, 1)
+      assert <<-9999.0::float-32-little, 42.0::float-32-little>> = data
+    end
+
+    test "marks every pixel valid without nodata" do
       {:ok, ds} = ExGdal.open(@tinymarble)
-      assert {:ok, nil} = ExGdal.metadata_item(ds, "NONEXISTENT_KEY")
+      assert {:ok, %{data: data, mask: mask}} = ExGdal.read_band_masked(ds, 1)
+      assert byte_size(data) == 4 * 100 * 50
+      assert mask == :binary.copy(<<255>>, 100 * 50)
+    end
+  end
+
+  describe "version/0" do
+    test "reports the linked GDAL" do
+      assert {:ok, %{release_name: name, version_num: num, build_info: info}} = ExGdal.version()
+      assert name =~ ~r/^\d+\.\d+/
+      assert num >= 3_000_000
+      assert is_map(info)
+    end
+  end
+
+  describe "native_endianness/0" do
+    test "reports the host byte order" do
+      assert {:ok, endianness} = ExGdal.native_endianness()
+      assert endianness == System.endianness()
     end
   end
+
+  describe "read_band_window/3" do
+    test "reads an {x, y, w, h} window like the flat form" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+
+      assert {:ok, window} = ExGdal.read_band_window(ds, 1, {5, 3, 4, 2})
+      assert {:ok, ^window} = ExGdal.read_band_window(ds, 1, 5, 3, 4, 2)
+      assert byte_size(window) == 4 * 2 * 4
+    end
+  end
+
+  describe "read_band_window/6" do
+    test "reads a sub-region" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, data} = ExGdal.read_band_window(ds, 1, 0, 0, 10, 10)
+      assert byte_size(data) == 10 * 10
+    end
+
+    test "reads float32 windows at the band's data type" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, {w, _h}} = ExGdal.raster_size(ds)
+      {:ok, full} = ExGdal.read_band(ds, 1)
+
+      assert {:ok, window} = ExGdal.read_band_window(ds, 1, 5, 3, 4, 2)
+      assert byte_size(window) == 4 * 2 * 4
+
+      expected =
+        for row <- 3..4, into: <<>>, do: binary_part(full, (row * w + 5) * 4, 4 * 4)
+
+      assert for(<<v::float-32-native <- window>>, do: v) ==
+               for(<<v::float-32-native <- expected>>, do: v)
+    end
+  end
+
+  describe "read_windows/3" do
+    test "returns each window in order" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      windows = [{0, 0, 10, 10}, {50, 20, 5, 3}, {90, 40, 10, 10}]
+
+      expected =
+        for window <- windows do
+          {:ok, data} = ExGdal.read_band_window(ds, 3, window)
+          data
+        end
+
+      assert {:ok, ^expected} = ExGdal.read_windows(ds, 3, windows)
+    end
+
+    test "reports the index of the first invalid window" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, reason}} =
+               ExGdal.read_windows(ds, 1, [{0, 0, 10, 10}, {95, 0, 10, 10}, {-1, 0, 1, 1}])
+
+      assert reason =~ "window 1"
+    end
+
+    test "rejects windows whose end overflows" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      huge = 0xFFFF_FFFF_FFFF_FFFF
+
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_windows(ds, 1, [{1, 0, huge, 1}])
+      assert {:error, {:invalid_argument, _}} = ExGdal.read_windows(ds, 1, [{0, 1, 1, huge}])
+    end
+  end
+
+  describe "band/2" do
+    test "reads through the cached band like the dataset readers" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, %ExGdal.Band{index: 2} = band} = ExGdal.band(ds, 2)
+
+      assert ExGdal.read_band(band) == ExGdal.read_band(ds, 2)
+      assert ExGdal.read_band_window(band, {10, 5, 20, 8}) ==
+               ExGdal.read_band_window(ds, 2, {10, 5, 20, 8})
+    end
+
+    test "returns error for an invalid band index" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 4, _}} = ExGdal.band(ds, 4)
+    end
+
+    test "fails reads once the dataset is closed" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, band} = ExGdal.band(ds, 1)
+      :ok = ExGdal.close(ds)
+      assert {:error, {:closed, _}} = ExGdal.read_band(band)
+    end
+  end
+
+  describe "read_band_window_resampled/5" do
+    test "resamples a window to the output size" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+
+      assert {:ok, tile} =
+               ExGdal.read_band_window_resampled(ds, 1, {10, 10, 200, 100}, {16, 8}, :bilinear)
+
+      assert byte_size(tile) == 16 * 8 * 4
+    end
+
+    test "matches read_band_window at native resolution" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, window} = ExGdal.read_band_window(ds, 1, {5, 5, 20, 10})
+      assert {:ok, ^window} = ExGdal.read_band_window_resampled(ds, 1, {5, 5, 20, 10}, {20, 10})
+    end
+  end
+
+  describe "raster_io/5" do
+    test "matches read_band_window with default spacing" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, window} = ExGdal.read_band_window(ds, 1, 5, 5, 20, 10)
+      assert {:ok, ^window} = ExGdal.raster_io(ds, 1, {5, 5, 20, 10}, {20, 10})
+    end
+
+    test "spreads pixels by the given strides" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, <<a, b, c, d, e, f, g, h>>} = ExGdal.read_band_window(ds, 2, 0, 0, 4, 2)
+
+      assert {:ok, <<^a, 0, ^b, 0, ^c, 0, ^d, 0, 0, 0, ^e, 0, ^f, 0, ^g, 0, ^h>>} =
+               ExGdal.raster_io(ds, 2, {0, 0, 4, 2}, {4, 2}, {2, 10})
+    end
+
+    test "rejects strides that overlap pixels" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.raster_io(ds, 1, {0, 0, 4, 4}, {4, 4}, {2, 0})
+    end
+
+    test "rejects sizes and strides that overflow the buffer length" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      huge = 0xFFFF_FFFF_FFFF_FFFF
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.raster_io(ds, 1, {0, 0, 4, 4}, {huge, 4}, {4, 0})
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.raster_io(ds, 1, {0, 0, 4, 4}, {4, 4}, {4, div(huge, 2)})
+    end
+
+    test "returns error for an invalid band index" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 4, 3}} = ExGdal.raster_io(ds, 4, {0, 0, 4, 4}, {4, 4})
+    end
+  end
+
+  describe "block_size/2" do
+    test "returns the strip size of a stripped GeoTIFF" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, {333, 6}} = ExGdal.block_size(ds, 1)
+    end
+  end
+
+  describe "read_block/4" do
+    test "matches the same rows of a full band read" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, {bx, by}} = ExGdal.block_size(ds, 1)
+      {:ok, full} = ExGdal.read_band(ds, 1)
+
+      assert {:ok, block} = ExGdal.read_block(ds, 1, 0, 1)
+      assert block == binary_part(full, bx * by * 4, bx * by * 4)
+    end
+
+    test "pads the last partial block to a full block" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, {bx, by}} = ExGdal.block_size(ds, 1)
+      {_w, h} = ds.raster_size
+      assert {:ok, block} = ExGdal.read_block(ds, 1, 0, div(h, by))
+      assert byte_size(block) == bx * by * 4
+    end
+
+    test "returns error for a block outside the raster" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, _} = ExGdal.read_block(ds, 1, 1, 0)
+    end
+  end
+
+  describe "stream_blocks/3" do
+    test "sends every block in row-major order, then :done" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert :ok = ExGdal.stream_blocks(ds, 2)
+
+      {:ok, expected_first} = ExGdal.read_block(ds, 2, 0, 0)
+      {:ok, expected_last} = ExGdal.read_block(ds, 2, 0, 1)
+      assert_receive {:block, 0, 0, ^expected_first}
+      assert_receive {:block, 0, 1, ^expected_last}
+      assert_receive :done
+      refute_received {:block, _, _, _}
+    end
+
+    test "sends an error for an invalid band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert :ok = ExGdal.stream_blocks(ds, 9)
+      assert_receive {:error, {:invalid_band, 9, 3}}
+      refute_received :done
+    end
+  end
+
+  describe "overview_count/2" do
+    test "is zero for a raster without pyramids" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, 0} = ExGdal.overview_count(ds, 1)
+    end
+  end
+
+  describe "overview_sizes/2" do
+    test "is empty for a raster without pyramids" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, []} = ExGdal.overview_sizes(ds, 1)
+    end
+  end
+
+  describe "build_overviews/3" do
+    @tag :tmp_dir
+    test "adds one overview per factor", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "pyramid.tif"), 16, 16, 1, :uint8)
+      :ok = ExGdal.write_band(ds, 1, :binary.copy(<<7>>, 16 * 16), :uint8)
+
+      assert :ok = ExGdal.build_overviews(ds, :average, [2, 4])
+      assert {:ok, 2} = ExGdal.overview_count(ds, 1)
+      assert {:ok, overview} = ExGdal.read_overview(ds, 1, 1)
+      assert overview == :binary.copy(<<7>>, 4 * 4)
+    end
+
+    @tag :tmp_dir
+    test "reports each level's size through overview_sizes/2", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "pyramid.tif"), 16, 8, 1, :uint8)
+      :ok = ExGdal.build_overviews(ds, :nearest, [2, 4])
+      assert {:ok, [{8, 4}, {4, 2}]} = ExGdal.overview_sizes(ds, 1)
+    end
+
+    @tag :tmp_dir
+    test "rejects an empty factor list", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "pyramid.tif"), 16, 16, 1, :uint8)
+      assert {:error, _} = ExGdal.build_overviews(ds, :nearest, [])
+    end
+  end
+
+  describe "read_overview/3" do
+    test "returns error for an out-of-range overview" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, {:invalid_argument, reason}} = ExGdal.read_overview(ds, 1, 0)
+      assert reason =~ "out of range"
+    end
+  end
+
+  describe "read_for_resolution/5" do
+    @tag :tmp_dir
+    test "reads the smallest overview that meets the output size", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "pyramid.tif"), 16, 16, 1, :uint8)
+      :ok = ExGdal.write_band(ds, 1, :binary.list_to_bin(Enum.to_list(0..255)), :uint8)
+      :ok = ExGdal.build_overviews(ds, :average, [2, 4])
+
+      assert ExGdal.read_for_resolution(ds, 1, {0, 0, 16, 16}, {4, 4}) ==
+               ExGdal.read_overview(ds, 1, 1)
+
+      assert ExGdal.read_for_resolution(ds, 1, {0, 0, 16, 16}, {8, 8}) ==
+               ExGdal.read_overview(ds, 1, 0)
+
+      assert ExGdal.read_for_resolution(ds, 1, {4, 4, 8, 8}, {8, 8}) ==
+               ExGdal.read_band_window(ds, 1, 4, 4, 8, 8)
+    end
+
+    test "returns error for a window outside the band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.read_for_resolution(ds, 1, {90, 0, 20, 10}, {10, 5})
+    end
+
+    test "rejects windows whose end overflows" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.read_for_resolution(ds, 1, {1, 0, 0xFFFF_FFFF_FFFF_FFFF, 10}, {10, 5})
+    end
+  end
+
+  describe "write_band/4" do
+    @tag :tmp_dir
+    test "round-trips float32 pixels", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "write.tif"), 4, 2, 1, :float32)
+      data = for v <- 1..8, into: <<>>, do: <<v * 0.5::float-32-native>>
+
+      assert :ok = ExGdal.write_band(ds, 1, data, :float32)
+      assert {:ok, ^data} = ExGdal.read_band(ds, 1)
+    end
+
+    @tag :tmp_dir
+    test "returns error when the binary size does not match", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "write.tif"), 4, 2, 1, :float32)
+      assert {:error, {:invalid_argument, reason}} =
+               ExGdal.write_band(ds, 1, <<0::size(8)-unit(8)>>, :float32)
+      assert reason =~ "expected 32"
+    end
+  end
+
+  describe "flush_cache/1" do
+    @tag :tmp_dir
+    test "persists written pixels while the dataset stays open", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "flush.tif")
+      {:ok, ds} = ExGdal.create("GTiff", path, 4, 2, 1, :uint8)
+      data = :binary.list_to_bin(Enum.to_list(1..8))
+      :ok = ExGdal.write_band(ds, 1, data, :uint8)
+
+      assert :ok = ExGdal.flush_cache(ds)
+      {:ok, reopened} = ExGdal.open(path)
+      assert {:ok, ^data} = ExGdal.read_band(reopened, 1)
+    end
+  end
+
+  describe "band_type/2" do
+    test "tinymarble bands are uint8" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, :uint8} = ExGdal.band_type(ds, 1)
+    end
+
+    test "dem-hills band is float32" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, :float32} = ExGdal.band_type(ds, 1)
+    end
+  end
+
+  describe "band_type_info/2" do
+    test "bundles size, signedness and endianness with the type" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, endianness} = ExGdal.native_endianness()
+
+      assert {:ok, %{type: :float32, size: 4, signed: true, endianness: ^endianness}} =
+               ExGdal.band_type_info(ds, 1)
+    end
+
+    test "reports byte bands as unsigned" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, %{type: :uint8, size: 1, signed: false}} = ExGdal.band_type_info(ds, 1)
+    end
+
+    test "describes the float64 bytes read from an Int64 band" do
+      vrt = """
+      <VRTDataset rasterXSize="100" rasterYSize="50">
+        <VRTRasterBand dataType="Int64" band="1">
+          <SimpleSource>
+            <SourceFilename relativeToVRT="0">#{Path.expand(@tinymarble)}</SourceFilename>
+            <SourceBand>1</SourceBand>
+          </SimpleSource>
+        </VRTRasterBand>
+      </VRTDataset>
+      """
+
+      {:ok, ds} = ExGdal.open_memory(vrt, "int64.vrt")
+      assert {:ok, %{type: :float64, size: 8, signed: true}} = ExGdal.band_type_info(ds, 1)
+      assert {:ok, "{:f, 64}"} = ExGdal.nx_type(ds, 1)
+      assert {:ok, data} = ExGdal.read_band(ds, 1)
+      assert byte_size(data) == 100 * 50 * 8
+    end
+  end
+
+  describe "nx_type/2" do
+    test "writes out the Nx type of the readers' bytes" do
+      {:ok, dem} = ExGdal.open(@dem_hills)
+      assert {:ok, "{:f, 32}"} = ExGdal.nx_type(dem, 1)
+
+      {:ok, marble} = ExGdal.open(@tinymarble)
+      assert {:ok, "{:u, 8}"} = ExGdal.nx_type(marble, 1)
+
+      {:ok, gcp} = ExGdal.open(@gcp)
+      assert {:ok, "{:u, 16}"} = ExGdal.nx_type(gcp, 1)
+    end
+
+    test "agrees with read_band_typed/2" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, %{type: :f32}} = ExGdal.read_band_typed(ds, 1)
+      assert {:ok, spec} = ExGdal.nx_type(ds, 1)
+      assert Code.eval_string(spec) == {{:f, 32}, []}
+    end
+
+    test "returns error for an invalid band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 9, 3}} = ExGdal.nx_type(ds, 9)
+    end
+  end
+
+  describe "data_type_size/1" do
+    test "returns bytes per element" do
+      types = [:uint8, :int16, :uint16, :int32, :uint32, :float32, :float64]
+      sizes = Enum.map(types, &ExGdal.data_type_size/1)
+      assert sizes == Enum.map([1, 2, 2, 4, 4, 4, 8], &{:ok, &1})
+    end
+
+    test "returns error for an unknown type" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.data_type_size(:unknown)
+    end
+  end
+
+  describe "band_color_interp/2" do
+    test "tinymarble bands are red, green, blue" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, :red} = ExGdal.band_color_interp(ds, 1)
+      assert {:ok, :green} = ExGdal.band_color_interp(ds, 2)
+      assert {:ok, :blue} = ExGdal.band_color_interp(ds, 3)
+    end
+
+    test "single-band dem is gray" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, :gray} = ExGdal.band_color_interp(ds, 1)
+    end
+  end
+
+  describe "set_band_color_interp/3" do
+    @tag :tmp_dir
+    test "marks a band as alpha", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "interp.tif"), 4, 4, 2, :uint8)
+      assert :ok = ExGdal.set_band_color_interp(ds, 2, :alpha)
+      assert {:ok, :alpha} = ExGdal.band_color_interp(ds, 2)
+    end
+
+    test "rejects an unknown interpretation" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, _} = ExGdal.set_band_color_interp(ds, 1, :purple)
+    end
+  end
+
+  describe "is_paletted/1" do
+    test "is false for true-color and gray datasets" do
+      {:ok, marble} = ExGdal.open(@tinymarble)
+      {:ok, dem} = ExGdal.open(@dem_hills)
+      assert {:ok, false} = ExGdal.is_paletted(marble)
+      assert {:ok, false} = ExGdal.is_paletted(dem)
+    end
+  end
+
+  describe "has_alpha/1" do
+    test "is nil without an alpha band" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, nil} = ExGdal.has_alpha(ds)
+    end
+
+    @tag :tmp_dir
+    test "returns the alpha band index", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "rgba.tif"), 4, 4, 4, :uint8)
+      :ok = ExGdal.set_band_color_interp(ds, 4, :alpha)
+      assert {:ok, 4} = ExGdal.has_alpha(ds)
+    end
+  end
+
+  describe "color_table/2" do
+    test "returns nil for a band without a palette" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, nil} = ExGdal.color_table(ds, 1)
+    end
+  end
+
+  describe "no_data_value/2" do
+    test "tinymarble has no nodata" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, nil} = ExGdal.no_data_value(ds, 1)
+    end
+  end
+
+  describe "set_no_data_value/3" do
+    @tag :tmp_dir
+    test "round-trips through no_data_value/2", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 4, 4, 1, :float32)
+      assert :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+      assert {:ok, -9999.0} = ExGdal.no_data_value(ds, 1)
+    end
+
+    test "rejects a read-only dataset instead of writing a sidecar" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_no_data_value(ds, 1, 0)
+      refute File.exists?(@tinymarble <> ".aux.xml")
+    end
+  end
+
+  describe "delete_no_data_value/2" do
+    @tag :tmp_dir
+    test "clears the nodata value", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 4, 4, 1, :float32)
+      :ok = ExGdal.set_no_data_value(ds, 1, 0)
+      assert :ok = ExGdal.delete_no_data_value(ds, 1)
+      assert {:ok, nil} = ExGdal.no_data_value(ds, 1)
+    end
+  end
+
+  describe "band_checksum/3" do
+    test "fingerprints band data" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, checksum} = ExGdal.band_checksum(ds, 1)
+      assert is_integer(checksum)
+      assert {:ok, ^checksum} = ExGdal.band_checksum(ds, 1, {0, 0, 100, 50})
+    end
+
+    test "returns error for invalid band index" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 4, _}} = ExGdal.band_checksum(ds, 4)
+    end
+  end
+
+  describe "band_statistics/3" do
+    test "returns exact statistics for a float32 band" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, %{min: min, max: max, mean: mean, std_dev: std_dev}} = ExGdal.band_statistics(ds, 1)
+      assert min <= mean and mean <= max
+      assert std_dev >= 0.0
+      # nodata (-999999) is excluded
+      assert min > -999_999.0
+    end
+
+    test "returns approximate statistics" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, %{min: min, max: max}} = ExGdal.band_statistics(ds, 1, true)
+      assert 0.0 <= min and max <= 255.0
+    end
+
+    @tag :tmp_dir
+    test "matches the mean and spread of the pixels", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "stats.tif"), 4, 1, 1, :int16)
+      data = for v <- [-9999, 2, 4, 6], into: <<>>, do: <<v::signed-16-native>>
+      :ok = ExGdal.write_band(ds, 1, data, :int16)
+      :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+
+      assert {:ok, %{min: 2.0, max: 6.0, mean: 4.0, std_dev: std_dev}} =
+               ExGdal.band_statistics(ds, 1)
+
+      assert_in_delta std_dev, :math.sqrt(8 / 3), 1.0e-12
+    end
+
+    @tag :tmp_dir
+    test "does not write a sidecar next to a read-only file", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "dem.tif")
+      File.cp!(@dem_hills, path)
+
+      {:ok, ds} = ExGdal.open(path)
+      assert {:ok, _} = ExGdal.band_statistics(ds, 1)
+      assert {:ok, _} = ExGdal.band_statistics(ds, 1, true)
+      :ok = ExGdal.close(ds)
+
+      refute File.exists?(path <> ".aux.xml")
+    end
+
+    @tag :tmp_dir
+    test "returns all_nodata when every pixel is nodata", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "fill.tif"), 2, 2, 1, :float32)
+      :ok = ExGdal.set_no_data_value(ds, 1, 0)
+
+      assert {:error, {:all_nodata, _}} = ExGdal.band_statistics(ds, 1)
+    end
+
+    test "returns error for invalid band index" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, _} = ExGdal.band_statistics(ds, 0)
+    end
+  end
+
+  describe "band_min_max/3" do
+    test "matches the range from full statistics" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, %{min: min, max: max}} = ExGdal.band_statistics(ds, 1)
+      assert {:ok, {^min, ^max}} = ExGdal.band_min_max(ds, 1)
+    end
+
+    test "supports the approximate algorithm" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, {min, max}} = ExGdal.band_min_max(ds, 1, true)
+      assert min <= max
+    end
+
+    @tag :tmp_dir
+    test "excludes nodata pixels", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "nodata.tif"), 3, 1, 1, :int16)
+      data = for v <- [-9999, 4, 7], into: <<>>, do: <<v::signed-16-native>>
+      :ok = ExGdal.write_band(ds, 1, data, :int16)
+      :ok = ExGdal.set_no_data_value(ds, 1, -9999)
+
+      assert {:ok, {4.0, 7.0}} = ExGdal.band_min_max(ds, 1)
+    end
+
+    @tag :tmp_dir
+    test "returns all_nodata when every pixel is nodata", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "fill.tif"), 2, 2, 1, :float32)
+      :ok = ExGdal.set_no_data_value(ds, 1, 0)
+
+      assert {:error, {:all_nodata, _}} = ExGdal.band_min_max(ds, 1)
+    end
+  end
+
+  describe "band_histogram/6" do
+    test "counts every uint8 pixel across the full range" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {w, h} = ds.raster_size
+      assert {:ok, counts} = ExGdal.band_histogram(ds, 1, -0.5, 255.5, 256)
+      assert length(counts) == 256
+      assert Enum.sum(counts) == w * h
+    end
+
+    test "works with fractional bounds on float32 bands" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, {min, max}} = ExGdal.band_min_max(ds, 1)
+      mid = (min + max) / 2
+
+      {:ok, inside} = ExGdal.band_histogram(ds, 1, min, mid, 10)
+      {:ok, clamped} = ExGdal.band_histogram(ds, 1, min, mid, 10, true)
+      assert length(inside) == 10
+      assert Enum.sum(clamped) > Enum.sum(inside)
+    end
+  end
+
+  describe "band_scale/2 and band_offset/2" do
+    test "default to identity when unset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, 1.0} = ExGdal.band_scale(ds, 1)
+      assert {:ok, +0.0} = ExGdal.band_offset(ds, 1)
+    end
+  end
+
+  describe "band_unit_type/2" do
+    test "is empty when unspecified" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, ""} = ExGdal.band_unit_type(ds, 1)
+    end
+  end
+
+  describe "band_category_names/2" do
+    @tag :tmp_dir
+    test "reads category names", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "classes.tif")
+      {:ok, out} = ExGdal.create("GTiff", path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+
+      File.write!(path <> ".aux.xml", """
+      <PAMDataset>
+        <PAMRasterBand band="1">
+          <CategoryNames>
+            <Category>water</Category>
+            <Category>forest</Category>
+          </CategoryNames>
+        </PAMRasterBand>
+      </PAMDataset>
+      """)
+
+      {:ok, ds} = ExGdal.open(path)
+      assert {:ok, ["water", "forest"]} = ExGdal.band_category_names(ds, 1)
+    end
+
+    test "is empty without categories" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, []} = ExGdal.band_category_names(ds, 1)
+    end
+  end
+
+  describe "band_rat/2" do
+    @tag :tmp_dir
+    test "reads the attribute table", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "classes.tif")
+      {:ok, out} = ExGdal.create("GTiff", path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+
+      File.write!(path <> ".aux.xml", """
+      <PAMDataset>
+        <PAMRasterBand band="1">
+          <GDALRasterAttributeTable>
+            <FieldDefn index="0"><Name>Value</Name><Type>0</Type><Usage>5</Usage></FieldDefn>
+            <FieldDefn index="1"><Name>Area</Name><Type>1</Type><Usage>0</Usage></FieldDefn>
+            <FieldDefn index="2"><Name>Class</Name><Type>2</Type><Usage>2</Usage></FieldDefn>
+            <Row index="0"><F>1</F><F>12.5</F><F>water</F></Row>
+            <Row index="1"><F>2</F><F>3.5</F><F>forest</F></Row>
+          </GDALRasterAttributeTable>
+        </PAMRasterBand>
+      </PAMDataset>
+      """)
+
+      {:ok, ds} = ExGdal.open(path)
+      assert {:ok, %{columns: columns, rows: rows}} = ExGdal.band_rat(ds, 1)
+
+      assert columns == [
+               %{name: "Value", type: :integer, usage: :min_max},
+               %{name: "Area", type: :real, usage: :generic},
+               %{name: "Class", type: :string, usage: :name}
+             ]
+
+      assert rows == [[1, 12.5, "water"], [2, 3.5, "forest"]]
+    end
+
+    test "is nil without an attribute table" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, nil} = ExGdal.band_rat(ds, 1)
+    end
+  end
+
+  describe "spatial_ref_wkt/1" do
+    test "returns WKT string for dataset with SRS" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, wkt} = ExGdal.spatial_ref_wkt(ds)
+      assert is_binary(wkt)
+      assert String.contains?(wkt, "GEOGCS") or String.contains?(wkt, "GEOGCRS")
+    end
+
+    test "returns error for dataset without SRS" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:no_spatial_ref, _}} = ExGdal.spatial_ref_wkt(ds)
+    end
+  end
+
+  describe "spatial_ref_proj4/1" do
+    test "returns proj4 string for dataset with SRS" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, proj4} = ExGdal.spatial_ref_proj4(ds)
+      assert is_binary(proj4)
+      assert String.contains?(proj4, "+proj")
+    end
+  end
+
+  describe "spatial_ref_epsg/1" do
+    test "returns an integer code or nil" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, code} = ExGdal.spatial_ref_epsg(ds)
+      assert is_nil(code) or is_integer(code)
+    end
+
+    test "returns error for dataset without SRS" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:no_spatial_ref, _}} = ExGdal.spatial_ref_epsg(ds)
+    end
+  end
+
+  describe "transform_coords/3" do
+    test "reprojects lon/lat to web mercator" do
+      assert {:ok, [{x0, y0}, {x, y}]} =
+               ExGdal.transform_coords(4326, 3857, [{0, 0}, {10.0, 0.0}])
+      assert_in_delta x0, 0.0, 1.0e-6
+      assert_in_delta y0, 0.0, 1.0e-6
+      assert_in_delta x, 1_113_194.9, 0.1
+      assert_in_delta y, 0.0, 1.0e-6
+    end
+
+    test "accepts definition strings" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, wkt} = ExGdal.spatial_ref_wkt(ds)
+      assert {:ok, [{x, y}]} = ExGdal.transform_coords(wkt, "EPSG:4326", [{-87.5, 39.4}])
+      assert_in_delta x, -87.5, 1.0e-3
+      assert_in_delta y, 39.4, 1.0e-3
+    end
+
+    test "returns error for an unknown spatial reference" do
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.transform_coords("not a crs", 4326, [{0, 0}])
+    end
+  end
+
+  describe "transform_with/2" do
+    test "reuses a transform across batches" do
+      assert {:ok, %ExGdal.CoordTransform{source: 4326, target: 3857} = transform} =
+               ExGdal.create_coord_transform(4326, 3857)
+
+      assert {:ok, [{x, _}]} = ExGdal.transform_with(transform, [{10, 0}])
+      assert_in_delta x, 1_113_194.9, 0.1
+
+      assert ExGdal.transform_with(transform, [{1.5, 2.5}, {-3, 4}]) ==
+               ExGdal.transform_coords(4326, 3857, [{1.5, 2.5}, {-3, 4}])
+    end
+
+    test "returns error for an unknown spatial reference" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.create_coord_transform(4326, "not a crs")
+    end
+  end
+
+  describe "set_spatial_ref/2" do
+    @tag :tmp_dir
+    test "accepts an EPSG code", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "srs.tif"), 4, 4, 1, :uint8)
+      assert :ok = ExGdal.set_spatial_ref(ds, 4326)
+      assert {:ok, 4326} = ExGdal.spatial_ref_epsg(ds)
+    end
+
+    @tag :tmp_dir
+    test "accepts a definition string", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "srs.tif"), 4, 4, 1, :uint8)
+      assert :ok = ExGdal.set_spatial_ref(ds, "+proj=longlat +datum=WGS84 +no_defs")
+      assert {:ok, wkt} = ExGdal.spatial_ref_wkt(ds)
+      assert wkt =~ "WGS"
+    end
+
+    @tag :tmp_dir
+    test "returns error for a definition that does not parse", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "srs.tif"), 4, 4, 1, :uint8)
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_spatial_ref(ds, "not a crs")
+    end
+  end
+
+  describe "gcps/1" do
+    test "reads ground control points" do
+      {:ok, ds} = ExGdal.open(@gcp)
+      assert {:ok, 210} = ExGdal.gcp_count(ds)
+      assert {:ok, gcps} = ExGdal.gcps(ds)
+      assert length(gcps) == 210
+      assert %{id: id, pixel: pixel, line: line, x: x, y: y, z: z} = hd(gcps)
+      assert is_binary(id)
+      assert Enum.all?([pixel, line, x, y, z], &is_float/1)
+    end
+
+    test "returns an empty list without GCPs" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, 0} = ExGdal.gcp_count(ds)
+      assert {:ok, []} = ExGdal.gcps(ds)
+    end
+  end
+
+  describe "gcp_spatial_ref_wkt/1" do
+    test "returns the GCP spatial reference" do
+      {:ok, ds} = ExGdal.open(@gcp)
+      assert {:ok, wkt} = ExGdal.gcp_spatial_ref_wkt(ds)
+      assert wkt =~ "WGS 84"
+    end
+
+    test "returns error without GCPs" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:no_spatial_ref, _}} = ExGdal.gcp_spatial_ref_wkt(ds)
+    end
+  end
+
+  describe "warp/3" do
+    test "reprojects into memory" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, warped} = ExGdal.warp(ds, 3857, resolution: {20, 20}, resampling: :bilinear)
+      assert warped.path == nil
+      assert warped.raster_count == 1
+      assert {:ok, 3857} = ExGdal.spatial_ref_epsg(warped)
+
+      assert {:ok, %ExGdal.GeoTransform{pixel_width: 20.0, pixel_height: -20.0}} =
+               ExGdal.geo_transform(warped)
+    end
+
+    @tag :tmp_dir
+    test "writes to a file", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "warped.tif")
+
+      assert {:ok, %ExGdal.Dataset{driver: "GTiff", path: ^path}} =
+               ExGdal.warp(ds, "EPSG:32616", path: path)
+
+      assert File.exists?(path)
+    end
+
+    test "returns error for an unsupported resampling" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, {:invalid_argument, _}} = ExGdal.warp(ds, 3857, resampling: :gauss)
+    end
+  end
+
+  describe "copy/4" do
+    @tag :tmp_dir
+    test "passes creation options to the driver", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      path = Path.join(tmp_dir, "compressed.tif")
+
+      assert {:ok, copy} = ExGdal.copy(ds, "GTiff", path, compress: "DEFLATE", tiled: true)
+      assert copy.raster_size == {100, 50}
+      assert {:ok, "DEFLATE"} = ExGdal.metadata_item(copy, "COMPRESSION", "IMAGE_STRUCTURE")
+      assert {:ok, {256, 256}} = ExGdal.block_size(copy, 1)
+      assert ExGdal.read_band(copy, 1) == ExGdal.read_band(ds, 1)
+    end
+
+    @tag :tmp_dir
+    test "returns error for an unknown driver", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.copy(ds, "NoSuchDriver", Path.join(tmp_dir, "x"))
+    end
+  end
+
+  describe "to_mem/1" do
+    test "returns a writable copy independent of the source" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, mem} = ExGdal.to_mem(ds)
+      assert mem.path == nil
+      assert mem.raster_size == {100, 50}
+      assert mem.raster_count == 3
+      assert ExGdal.read_band(mem, 1) == ExGdal.read_band(ds, 1)
+
+      assert :ok = ExGdal.write_band(mem, 1, :binary.copy(<<7>>, 100 * 50), :uint8)
+      assert {:ok, <<7, _::binary>>} = ExGdal.read_band(mem, 1)
+      assert ExGdal.read_band(ds, 1) != ExGdal.read_band(mem, 1)
+    end
+
+    test "keeps georeferencing" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, mem} = ExGdal.to_mem(ds)
+      assert ExGdal.geo_transform(mem) == ExGdal.geo_transform(ds)
+      assert ExGdal.no_data_value(mem, 1) == ExGdal.no_data_value(ds, 1)
+    end
+  end
+
+  describe "create_copy_progress/5" do
+    @tag :tmp_dir
+    test "sends rising progress then :done", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "progress.tif")
+
+      assert {:ok, copy} = ExGdal.create_copy_progress(ds, "GTiff", path, compress: "DEFLATE")
+      assert copy.raster_size == {333, 218}
+
+      # The copy is synchronous, so every message has arrived by now
+      {:messages, messages} = Process.info(self(), :messages)
+      assert List.last(messages) == :done
+      fractions = for {:progress, fraction} <- messages, do: fraction
+      assert fractions != []
+      assert fractions == Enum.sort(fractions)
+      assert List.last(fractions) == 1.0
+    end
+
+    @tag :tmp_dir
+    test "reports progress from a multithreaded COG copy", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "threaded_cog.tif")
+      options = [compress: "DEFLATE", num_threads: "ALL_CPUS", blocksize: 64]
+
+      assert {:ok, copy} = ExGdal.create_copy_progress(ds, "COG", path, options)
+      assert ExGdal.read_band(copy, 1) == ExGdal.read_band(ds, 1)
+
+      {:messages, messages} = Process.info(self(), :messages)
+      assert List.last(messages) == :done
+      fractions = for {:progress, fraction} <- messages, do: fraction
+      assert fractions != []
+      assert fractions == Enum.sort(fractions)
+      assert List.last(fractions) == 1.0
+    end
+
+    @tag :tmp_dir
+    test "returns error for an unknown driver without :done", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.create_copy_progress(ds, "NoSuchDriver", Path.join(tmp_dir, "x"))
+
+      refute_received :done
+    end
+  end
+
+  describe "translate/2" do
+    test "extracts a band and window" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, sub} = ExGdal.translate(ds, bands: [2], window: {10, 5, 20, 8})
+      assert sub.raster_count == 1
+      assert sub.raster_size == {20, 8}
+      assert ExGdal.read_band(sub, 1) == ExGdal.read_band_window(ds, 2, 10, 5, 20, 8)
+    end
+
+    test "converts the data type with scaling" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, bytes} = ExGdal.translate(ds, type: :uint8, scale: {0, 1000, 0, 255})
+      assert {:ok, :uint8} = ExGdal.band_type(bytes, 1)
+    end
+
+    @tag :tmp_dir
+    test "writes to a file", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      path = Path.join(tmp_dir, "marble.png")
+
+      assert {:ok, %ExGdal.Dataset{driver: "PNG"}} =
+               ExGdal.translate(ds, path: path, driver: "PNG")
+
+      assert File.exists?(path)
+    end
+
+    test "returns error for a band out of range" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_band, 9, _}} = ExGdal.translate(ds, bands: [9])
+    end
+  end
+
+  describe "encode_window/5" do
+    test "encodes a PNG at the output size" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, png} = ExGdal.encode_window(ds, {0, 0, 100, 50}, {50, 25}, :png)
+      assert <<0x89, "PNG", _::binary>> = png
+
+      {:ok, decoded} = ExGdal.open_memory(png, "tile.png")
+      assert decoded.raster_size == {50, 25}
+      assert decoded.raster_count == 3
+    end
+
+    test "encodes a JPEG with a quality" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, jpeg} = ExGdal.encode_window(ds, {10, 5, 32, 32}, {32, 32}, :jpeg, quality: 90)
+      assert <<0xFF, 0xD8, _::binary>> = jpeg
+    end
+
+    test "rejects an unknown format or a PNG quality" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.encode_window(ds, {0, 0, 10, 10}, {10, 10}, :gif)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.encode_window(ds, {0, 0, 10, 10}, {10, 10}, :png, quality: 80)
+    end
+  end
+
+  describe "dem_processing/4" do
+    @tag :tmp_dir
+    test "renders a hillshade with the given light source", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "hillshade.tif")
+
+      assert {:ok, shade} =
+               ExGdal.dem_processing(ds, :hillshade, path,
+                 scale: 111_120,
+                 azimuth: 270,
+                 altitude: 30
+               )
+
+      assert shade.raster_size == {333, 218}
+      assert {:ok, :uint8} = ExGdal.band_type(shade, 1)
+      assert File.exists?(path)
+    end
+
+    @tag :tmp_dir
+    test "computes slope as float32", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, slope} = ExGdal.dem_processing(ds, :slope, Path.join(tmp_dir, "slope.tif"))
+      assert {:ok, :float32} = ExGdal.band_type(slope, 1)
+    end
+
+    @tag :tmp_dir
+    test "rejects lighting options outside hillshade", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "aspect.tif")
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.dem_processing(ds, :aspect, path, azimuth: 90)
+    end
+
+    @tag :tmp_dir
+    test "returns error for an unknown mode", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      path = Path.join(tmp_dir, "relief.tif")
+      assert {:error, {:invalid_argument, _}} = ExGdal.dem_processing(ds, :relief, path)
+    end
+  end
+
+  describe "geo_transform/1" do
+    test "returns GeoTransform struct" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, %ExGdal.GeoTransform{} = gt} = ExGdal.geo_transform(ds)
+      assert is_float(gt.origin_x)
+      assert is_float(gt.origin_y)
+      assert is_float(gt.pixel_width)
+      assert is_float(gt.pixel_height)
+    end
+  end
+
+  describe "geo_transform_map/1" do
+    test "labels the coefficients like the struct" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, gt} = ExGdal.geo_transform(ds)
+      assert {:ok, map} = ExGdal.geo_transform_map(ds)
+
+      assert map == %{
+               origin_x: gt.origin_x,
+               pixel_width: gt.pixel_width,
+               row_rotation: gt.skew_x,
+               origin_y: gt.origin_y,
+               col_rotation: gt.skew_y,
+               pixel_height: gt.pixel_height
+             }
+    end
+
+    test "returns error for an ungeoreferenced raster" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, _} = ExGdal.geo_transform_map(ds)
+    end
+  end
+
+  describe "set_geo_transform/2" do
+    @tag :tmp_dir
+    test "round-trips through geo_transform/1", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "georef.tif"), 4, 4, 1, :float32)
+      assert :ok = ExGdal.set_geo_transform(ds, [500_000, 30, 0, 4_200_000, 0, -30])
+
+      assert {:ok, %ExGdal.GeoTransform{origin_x: 500_000.0, pixel_height: -30.0} = gt} =
+               ExGdal.geo_transform(ds)
+
+      assert :ok = ExGdal.set_geo_transform(ds, %{gt | origin_x: 0.0})
+      assert {:ok, %ExGdal.GeoTransform{origin_x: 0.0}} = ExGdal.geo_transform(ds)
+    end
+
+    @tag :tmp_dir
+    test "returns error for the wrong number of coefficients", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "georef.tif"), 4, 4, 1, :float32)
+      assert {:error, {:invalid_argument, _}} = ExGdal.set_geo_transform(ds, [0, 1, 0, 0])
+    end
+  end
+
+  describe "pixel_to_geo/3" do
+    test "maps the top-left corner to the origin" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, gt} = ExGdal.geo_transform(ds)
+      assert {:ok, {x, y}} = ExGdal.pixel_to_geo(ds, 0, 0)
+      assert_in_delta x, gt.origin_x, 1.0e-9
+      assert_in_delta y, gt.origin_y, 1.0e-9
+    end
+
+    test "returns error for dataset without a geo-transform" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, _} = ExGdal.pixel_to_geo(ds, 0, 0)
+    end
+  end
+
+  describe "geo_to_pixel/3" do
+    test "inverts pixel_to_geo" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, {x, y}} = ExGdal.pixel_to_geo(ds, 12.5, 40.25)
+      assert {:ok, {col, row}} = ExGdal.geo_to_pixel(ds, x, y)
+      assert_in_delta col, 12.5, 1.0e-6
+      assert_in_delta row, 40.25, 1.0e-6
+    end
+  end
+
+  describe "driver_name/1" do
+    test "returns GTiff for GeoTIFF" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, "GTiff"} = ExGdal.driver_name(ds)
+    end
+  end
+
+  describe "info/1" do
+    test "summarizes an ungeoreferenced RGB dataset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, info} = ExGdal.info(ds)
+      assert info.raster_size == {100, 50}
+      assert info.band_count == 3
+      assert info.driver == "GTiff"
+      assert info.geo_transform == nil
+      assert info.spatial_ref_wkt == nil
+      assert Enum.all?(info.bands, &(&1 == %{type: :uint8, no_data_value: nil}))
+      assert length(info.bands) == 3
+    end
+
+    test "includes geo-transform, SRS and nodata when present" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, info} = ExGdal.info(ds)
+      assert %ExGdal.GeoTransform{} = info.geo_transform
+      assert {:ok, info.geo_transform} == ExGdal.geo_transform(ds)
+      assert is_binary(info.spatial_ref_wkt)
+      assert [%{type: :float32, no_data_value: -999_999.0}] = info.bands
+    end
+  end
+
+  describe "file_list/1" do
+    test "starts with the primary file" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, [primary | _]} = ExGdal.file_list(ds)
+      assert Path.basename(primary) == "tinymarble.tif"
+    end
+
+    @tag :tmp_dir
+    test "includes sidecar files", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "sidecar.tif")
+      {:ok, out} = ExGdal.create("GTiff", path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+      File.write!(path <> ".aux.xml", "<PAMDataset></PAMDataset>\n")
+
+      {:ok, ds} = ExGdal.open(path)
+      assert {:ok, files} = ExGdal.file_list(ds)
+      assert Enum.map(files, &Path.basename/1) == ["sidecar.tif", "sidecar.tif.aux.xml"]
+    end
+
+    test "is empty for an in-memory dataset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      {:ok, mem} = ExGdal.translate(ds, bands: [1])
+      assert {:ok, []} = ExGdal.file_list(mem)
+    end
+  end
+
+  describe "delete/2" do
+    @tag :tmp_dir
+    test "removes the dataset with its sidecars", %{tmp_dir: tmp_dir} do
+      path = Path.join(tmp_dir, "doomed.tif")
+      {:ok, out} = ExGdal.create("GTiff", path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+      File.write!(path <> ".aux.xml", "<PAMDataset></PAMDataset>\n")
+
+      assert :ok = ExGdal.delete("GTiff", path)
+      refute File.exists?(path)
+      refute File.exists?(path <> ".aux.xml")
+    end
+
+    @tag :tmp_dir
+    test "returns error for a missing dataset", %{tmp_dir: tmp_dir} do
+      assert {:error, _} = ExGdal.delete("GTiff", Path.join(tmp_dir, "missing.tif"))
+    end
+
+    test "returns error for an unknown driver" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.delete("NoSuchDriver", "x.tif")
+    end
+  end
+
+  describe "rename/3" do
+    @tag :tmp_dir
+    test "moves the dataset with its sidecars", %{tmp_dir: tmp_dir} do
+      old_path = Path.join(tmp_dir, "before.tif")
+      new_path = Path.join(tmp_dir, "after.tif")
+      {:ok, out} = ExGdal.create("GTiff", old_path, 4, 4, 1, :uint8)
+      :ok = ExGdal.close(out)
+      File.write!(old_path <> ".aux.xml", "<PAMDataset></PAMDataset>\n")
+
+      assert :ok = ExGdal.rename("GTiff", new_path, old_path)
+      refute File.exists?(old_path)
+      refute File.exists?(old_path <> ".aux.xml")
+      assert File.exists?(new_path <> ".aux.xml")
+      assert {:ok, %ExGdal.Dataset{raster_size: {4, 4}}} = ExGdal.open(new_path)
+    end
+
+    @tag :tmp_dir
+    test "returns error for a missing dataset", %{tmp_dir: tmp_dir} do
+      assert {:error, _} =
+               ExGdal.rename("GTiff", Path.join(tmp_dir, "b.tif"), Path.join(tmp_dir, "a.tif"))
+    end
+  end
+
+  describe "description/1" do
+    test "returns the opened path" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, description} = ExGdal.description(ds)
+      assert description == Path.expand(@tinymarble)
+    end
+
+    test "returns the /vsimem name of an in-memory dataset" do
+      {:ok, ds} = ExGdal.open_memory(File.read!(@tinymarble), "marble.tif")
+      assert {:ok, "/vsimem/" <> rest} = ExGdal.description(ds)
+      assert String.ends_with?(rest, "/marble.tif")
+    end
+  end
+
+  describe "set_band_description/3" do
+    @tag :tmp_dir
+    test "round-trips through band_description/2", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "described.tif"), 4, 4, 2, :float32)
+      assert :ok = ExGdal.set_band_description(ds, 2, "Band 4 - NIR")
+      assert {:ok, "Band 4 - NIR"} = ExGdal.band_description(ds, 2)
+      assert {:ok, ["", "Band 4 - NIR"]} = ExGdal.band_descriptions(ds)
+    end
+  end
+
+  describe "driver_list/0" do
+    test "includes writable GeoTIFF and copy-only PNG" do
+      assert {:ok, drivers} = ExGdal.driver_list()
+      gtiff = Enum.find(drivers, &(&1.short_name == "GTiff"))
+      assert %{long_name: "GeoTIFF", can_create: true, can_create_copy: true} = gtiff
+      png = Enum.find(drivers, &(&1.short_name == "PNG"))
+      assert %{can_create: false, can_create_copy: true} = png
+    end
+  end
+
+  describe "driver_metadata/1" do
+    test "returns extensions and MIME type" do
+      assert {:ok, %{extensions: ["tif" | _], mime_type: "image/tiff"}} =
+               ExGdal.driver_metadata("GTiff")
+    end
+
+    test "returns error for an unknown driver" do
+      assert {:error, {:invalid_argument, _}} = ExGdal.driver_metadata("NoSuchDriver")
+    end
+  end
+
+  describe "layer_count/1" do
+    test "counts vector layers" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, 1} = ExGdal.layer_count(ds)
+    end
+
+    test "is zero for a raster" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, 0} = ExGdal.layer_count(ds)
+    end
+  end
+
+  describe "layer_names/1" do
+    test "lists layer names" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, ["places"]} = ExGdal.layer_names(ds)
+    end
+  end
+
+  describe "layer_feature_count/2" do
+    test "counts features" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, 3} = ExGdal.layer_feature_count(ds, 0)
+    end
+
+    test "returns error for invalid layer index" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:error, {:invalid_layer, _}} = ExGdal.layer_feature_count(ds, 1)
+    end
+  end
+
+  describe "read_features/3" do
+    test "converts geometry and fields" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, [first, second]} = ExGdal.read_features(ds, 0, 2)
+
+      assert %{fid: 0, geometry_wkt: "POINT (-89.65 39.8)", fields: fields} = first
+      assert fields == %{"name" => "Springfield", "population" => 116_250, "elevation" => 182.5}
+      assert second.fields["name"] == "Peoria"
+    end
+
+    test "stops at the end of the layer" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, features} = ExGdal.read_features(ds, 0, 100)
+      assert length(features) == 3
+    end
+  end
+
+  describe "layer_geometry_type/2" do
+    test "returns the layer geometry type" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, :point} = ExGdal.layer_geometry_type(ds, 0)
+    end
+  end
+
+  describe "layer_extent/2" do
+    test "bounds all features" do
+      {:ok, ds} = ExGdal.open(@places)
+      assert {:ok, {min_x, min_y, max_x, max_y}} = ExGdal.layer_extent(ds, 0)
+      assert_in_delta min_x, -89.65, 1.0e-9
+      assert_in_delta min_y, 39.8, 1.0e-9
+      assert_in_delta max_x, -88.24, 1.0e-9
+      assert_in_delta max_y, 40.69, 1.0e-9
+    end
+  end
+
+  describe "rasterize_layer/4" do
+    setup %{tmp_dir: tmp_dir} do
+      {:ok, places} = ExGdal.open(@places)
+      %{places: places, path: Path.join(tmp_dir, "burned.tif")}
+    end
+
+    @tag :tmp_dir
+    test "burns a constant value", %{places: places, path: path} do
+      {:ok, target} = ExGdal.create("GTiff", path, 10, 10, 1, :uint8)
+      :ok = ExGdal.set_geo_transform(target, [-90, 0.2, 0, 41, 0, -0.2])
+
+      assert :ok = ExGdal.rasterize_layer(target, places, 0, burn_value: 255)
+      {:ok, data} = ExGdal.read_band(target, 1)
+      assert Enum.count(:binary.bin_to_list(data), &(&1 == 255)) == 3
+    end
+
+    @tag :tmp_dir
+    test "burns an attribute", %{places: places, path: path} do
+      {:ok, target} = ExGdal.create("GTiff", path, 10, 10, 1, :float32)
+      :ok = ExGdal.set_geo_transform(target, [-90, 0.2, 0, 41, 0, -0.2])
+
+      assert :ok = ExGdal.rasterize_layer(target, places, 0, attribute: "population")
+      {:ok, data} = ExGdal.read_band(target, 1)
+      burned = for <<v::float-32-native <- data>>, v != 0, do: v
+      assert Enum.sort(burned) == [88_302.0, 113_150.0, 116_250.0]
+    end
+
+    @tag :tmp_dir
+    test "returns error for an unknown attribute", %{places: places, path: path} do
+      {:ok, target} = ExGdal.create("GTiff", path, 10, 10, 1, :float32)
+      :ok = ExGdal.set_geo_transform(target, [-90, 0.2, 0, 41, 0, -0.2])
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.rasterize_layer(target, places, 0, attribute: "missing")
+    end
+
+    @tag :tmp_dir
+    test "returns invalid_band for a band the target lacks", %{places: places, path: path} do
+      {:ok, target} = ExGdal.create("GTiff", path, 10, 10, 1, :uint8)
+      :ok = ExGdal.set_geo_transform(target, [-90, 0.2, 0, 41, 0, -0.2])
+
+      assert {:error, {:invalid_band, 2, 1}} = ExGdal.rasterize_layer(target, places, 0, band: 2)
+    end
+
+    @tag :tmp_dir
+    test "burns a layer into a raster of the same GeoPackage", %{tmp_dir: tmp_dir} do
+      {:ok, classes} = ExGdal.create("GTiff", Path.join(tmp_dir, "classes.tif"), 4, 4, 1, :uint8)
+      :ok = ExGdal.set_geo_transform(classes, [0, 1, 0, 4, 0, -1])
+      data = :binary.copy(<<1, 1, 2, 2>>, 4)
+      :ok = ExGdal.write_band(classes, 1, data, :uint8)
+
+      {:ok, gpkg} = ExGdal.create("GPKG", Path.join(tmp_dir, "both.gpkg"), 4, 4, 1, :uint8)
+      :ok = ExGdal.set_geo_transform(gpkg, [0, 1, 0, 4, 0, -1])
+      :ok = ExGdal.write_band(gpkg, 1, :binary.copy(<<0>>, 16), :uint8)
+      {:ok, layer} = ExGdal.polygonize(classes, 1, gpkg, field: "class")
+
+      assert :ok = ExGdal.rasterize_layer(gpkg, gpkg, layer, attribute: "class")
+      assert {:ok, ^data} = ExGdal.read_band(gpkg, 1)
+    end
+  end
+
+  describe "polygonize/4" do
+    @tag :tmp_dir
+    test "emits one feature per region", %{tmp_dir: tmp_dir} do
+      {:ok, raster} = ExGdal.create("GTiff", Path.join(tmp_dir, "classes.tif"), 4, 4, 1, :uint8)
+      :ok = ExGdal.set_geo_transform(raster, [0, 1, 0, 4, 0, -1])
+      row = <<1, 1, 2, 2>>
+      :ok = ExGdal.write_band(raster, 1, :binary.copy(row, 4), :uint8)
+
+      {:ok, vec} = ExGdal.create_vector("GPKG", Path.join(tmp_dir, "classes.gpkg"))
+      assert {:ok, 0} = ExGdal.polygonize(raster, 1, vec, field: "class")
+      assert {:ok, ["polygons"]} = ExGdal.layer_names(vec)
+      assert {:ok, :polygon} = ExGdal.layer_geometry_type(vec, 0)
+
+      {:ok, features} = ExGdal.read_features(vec, 0, 10)
+      assert features |> Enum.map(& &1.fields["class"]) |> Enum.sort() == [1, 2]
+    end
+
+    @tag :tmp_dir
+    test "returns error when source and target are the same", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "classes.tif"), 4, 4, 1, :uint8)
+      assert {:error, {:invalid_argument, _}} = ExGdal.polygonize(ds, 1, ds)
+    end
+
+    @tag :tmp_dir
+    test "returns error for a read-only target", %{tmp_dir: tmp_dir} do
+      {:ok, raster} = ExGdal.create("GTiff", Path.join(tmp_dir, "classes.tif"), 4, 4, 1, :uint8)
+      {:ok, places} = ExGdal.open(@places)
+      assert {:error, {:invalid_argument, _}} = ExGdal.polygonize(raster, 1, places)
+    end
+
+    @tag :tmp_dir
+    test "runs alongside a polygonize in the other direction", %{tmp_dir: tmp_dir} do
+      # GeoPackages hold both rasters and vector layers, so each can be the
+      # source of one call and the target of the other
+      [a, b] =
+        for name <- ["a", "b"] do
+          {:ok, ds} = ExGdal.create("GPKG", Path.join(tmp_dir, "#{name}.gpkg"), 4, 4, 1, :uint8)
+          :ok = ExGdal.set_geo_transform(ds, [0, 1, 0, 4, 0, -1])
+          :ok = ExGdal.write_band(ds, 1, :binary.copy(<<1>>, 16), :uint8)
+          ds
+        end
+
+      results =
+        [{a, b}, {b, a}]
+        |> Stream.cycle()
+        |> Stream.take(20)
+        |> Stream.with_index()
+        |> Task.async_stream(
+          fn {{source, target}, i} ->
+            ExGdal.polygonize(source, 1, target, layer: "polygons_#{i}")
+          end,
+          timeout: 30_000
+        )
+        |> Enum.to_list()
+
+      assert Enum.all?(results, &match?({:ok, {:ok, _}}, &1))
+    end
+  end
+
+  describe "fill_nodata/4" do
+    @tag :tmp_dir
+    test "interpolates nodata pixels from their neighbours", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "voids.tif"), 3, 3, 1, :float32)
+      :ok = ExGdal.set_no_data_value(ds, 1, -1)
+      data = for v <- [5, 5, 5, 5, -1, 5, 5, 5, 5], into: <<>>, do: <<v::float-32-native>>
+      :ok = ExGdal.write_band(ds, 1, data, :float32)
+
+      assert :ok = ExGdal.fill_nodata(ds, 1, 10)
+      {:ok, data} = ExGdal.read_band(ds, 1)
+      assert for(<<v::float-32-native <- data>>, do: v) == List.duplicate(5.0, 9)
+    end
+
+    test "returns error for a read-only dataset" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, {:invalid_argument, reason}} = ExGdal.fill_nodata(ds, 1, 10)
+      assert reason =~ "read-only"
+    end
+  end
+
+  describe "sieve_filter/4" do
+    @tag :tmp_dir
+    test "merges regions below the threshold into their neighbours", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "speckle.tif"), 3, 3, 1, :uint8)
+      :ok = ExGdal.write_band(ds, 1, <<1, 1, 1, 1, 2, 1, 1, 1, 1>>, :uint8)
+
+      assert :ok = ExGdal.sieve_filter(ds, 1, 2, 8)
+      assert {:ok, <<1, 1, 1, 1, 1, 1, 1, 1, 1>>} = ExGdal.read_band(ds, 1)
+    end
+
+    @tag :tmp_dir
+    test "rejects connectedness other than 4 or 8", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "speckle.tif"), 3, 3, 1, :uint8)
+      assert {:error, {:invalid_argument, _}} = ExGdal.sieve_filter(ds, 1, 2, 6)
+    end
+
+    test "returns error for a read-only dataset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, reason}} = ExGdal.sieve_filter(ds, 1, 2)
+      assert reason =~ "read-only"
+    end
+  end
+
+  describe "contour/5" do
+    @tag :tmp_dir
+    test "traces lines at each interval with their level", %{tmp_dir: tmp_dir} do
+      {:ok, raster} = ExGdal.create("GTiff", Path.join(tmp_dir, "ramp.tif"), 4, 4, 1, :float32)
+      :ok = ExGdal.set_geo_transform(raster, [0, 1, 0, 4, 0, -1])
+      row = for v <- [5, 15, 25, 35], into: <<>>, do: <<v::float-32-native>>
+      :ok = ExGdal.write_band(raster, 1, :binary.copy(row, 4), :float32)
+
+      {:ok, vec} = ExGdal.create_vector("GPKG", Path.join(tmp_dir, "contours.gpkg"))
+      assert {:ok, 0} = ExGdal.contour(raster, 1, 10, vec)
+      assert {:ok, :line_string} = ExGdal.layer_geometry_type(vec, 0)
+
+      {:ok, features} = ExGdal.read_features(vec, 0, 10)
+      assert features |> Enum.map(& &1.fields["elev"]) |> Enum.sort() == [10.0, 20.0, 30.0]
+    end
+
+    @tag :tmp_dir
+    test "accepts an explicit list of levels", %{tmp_dir: tmp_dir} do
+      {:ok, dem} = ExGdal.open(@dem_hills)
+      {:ok, vec} = ExGdal.create_vector("GPKG", Path.join(tmp_dir, "contours.gpkg"))
+      {:ok, %{min: min, max: max}} = ExGdal.band_statistics(dem, 1)
+      level = (min + max) / 2
+
+      assert {:ok, 0} = ExGdal.contour(dem, 1, [level], vec, field: "height")
+      {:ok, features} = ExGdal.read_features(vec, 0, 1000)
+      assert features != []
+      assert Enum.all?(features, &(&1.fields["height"] == level))
+    end
+
+    @tag :tmp_dir
+    test "returns error for a non-positive interval", %{tmp_dir: tmp_dir} do
+      {:ok, dem} = ExGdal.open(@dem_hills)
+      {:ok, vec} = ExGdal.create_vector("GPKG", Path.join(tmp_dir, "contours.gpkg"))
+      assert {:error, {:invalid_argument, _}} = ExGdal.contour(dem, 1, 0, vec)
+    end
+
+    test "returns error for a read-only target" do
+      {:ok, dem} = ExGdal.open(@dem_hills)
+      {:ok, places} = ExGdal.open(@places)
+      assert {:error, {:invalid_argument, _}} = ExGdal.contour(dem, 1, 10, places)
+    end
+  end
+
+  describe "create_vector/2" do
+    @tag :tmp_dir
+    test "returns error for a raster-only driver", %{tmp_dir: tmp_dir} do
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.create_vector("GTiff", Path.join(tmp_dir, "out.tif"))
+    end
+  end
+
+  describe "metadata_item/3" do
+    test "returns nil for missing key" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, nil} = ExGdal.metadata_item(ds, "NONEXISTENT_KEY")
+    end
+  end
+
+  describe "set_metadata_item/4" do
+    @tag :tmp_dir
+    test "round-trips through metadata_item/3", %{tmp_dir: tmp_dir} do
+      {:ok, ds} = ExGdal.create("GTiff", Path.join(tmp_dir, "meta.tif"), 2, 2, 1, :uint8)
+      assert :ok = ExGdal.set_metadata_item(ds, "SOURCE", "dem-hills")
+      assert :ok = ExGdal.set_metadata_item(ds, "VERSION", "1.2", "PROVENANCE")
+      assert {:ok, "dem-hills"} = ExGdal.metadata_item(ds, "SOURCE")
+      assert {:ok, "1.2"} = ExGdal.metadata_item(ds, "VERSION", "PROVENANCE")
+    end
+
+    test "returns error for a read-only dataset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+
+      assert {:error, {:invalid_argument, _}} =
+               ExGdal.set_metadata_item(ds, "SOURCE", "marble")
+    end
+  end
+
+  describe "metadata_domains/1" do
+    test "lists domain names" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, domains} = ExGdal.metadata_domains(ds)
+      assert "IMAGE_STRUCTURE" in domains
+    end
+  end
+
+  describe "band_metadata/3" do
+    test "includes statistics cached on the band" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      {:ok, %{min: min}} = ExGdal.band_statistics(ds, 1)
+      assert {:ok, items} = ExGdal.band_metadata(ds, 1)
+      assert {"STATISTICS_MINIMUM", value} = List.keyfind(items, "STATISTICS_MINIMUM", 0)
+      assert {parsed, ""} = Float.parse(value)
+      assert_in_delta parsed, min, 1.0e-6
+    end
+
+    test "returns error for invalid band index" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:error, {:invalid_band, 2, _}} = ExGdal.band_metadata(ds, 2)
+    end
+  end
+
+  describe "band_metadata_item/4" do
+    test "returns nil for missing key" do
+      {:ok, ds} = ExGdal.open(@dem_hills)
+      assert {:ok, nil} = ExGdal.band_metadata_item(ds, 1, "NONEXISTENT_KEY")
+    end
+  end
+
+  describe "subdatasets/1" do
+    test "returns an empty list for a plain GeoTIFF" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, []} = ExGdal.subdatasets(ds)
+    end
+  end
+
+  describe "open_subdataset/2" do
+    test "returns error for a name that is not a subdataset" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:error, {:invalid_argument, _}} = ExGdal.open_subdataset(ds, @dem_hills)
+    end
+  end
+
+  describe "metadata/2" do
+    test "returns key-value pairs for a domain" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, items} = ExGdal.metadata(ds, "IMAGE_STRUCTURE")
+      assert {"INTERLEAVE", "PIXEL"} in items
+    end
+
+    test "returns an empty list for a missing domain" do
+      {:ok, ds} = ExGdal.open(@tinymarble)
+      assert {:ok, []} = ExGdal.metadata(ds, "NO_SUCH_DOMAIN")
+    end
+  end
+
+  # A zip holding tinymarble under rasters/ next to a non-raster file
+  defp zip_fixture(dir) do
+    archive = Path.join(dir, "bundle.zip")
+
+    {:ok, _} =
+      :zip.create(String.to_charlist(archive), [
+        {~c"rasters/marble.tif", File.read!(@tinymarble)},
+        {~c"README.txt", "not a raster\n"}
+      ])
+
+    archive
+  end
 end